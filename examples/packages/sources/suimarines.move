@@ -120,4 +120,9 @@ module gutenberg::suimarines {
 
         inventory::deposit_nft(inventory, nft);
     }
+
+    #[test_only]
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init(SUIMARINES {}, ctx);
+    }
 }
@@ -159,4 +159,9 @@ module gutenberg::suitraders {
 
         inventory::deposit_nft(inventory, nft);
     }
+
+    #[test_only]
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init(SUITRADERS {}, ctx);
+    }
 }
@@ -0,0 +1,88 @@
+#[test_only]
+module gutenberg::suimarines_tests {
+    use std::string;
+
+    use sui::test_scenario;
+    use sui::transfer;
+
+    use nft_protocol::royalty;
+    use nft_protocol::inventory;
+    use nft_protocol::collection::{Collection, MintCap};
+
+    use gutenberg::suimarines::{Self, SUIMARINES};
+
+    const CREATOR: address = @0xA1C05;
+
+    #[test]
+    fun it_inits_collection() {
+        let scenario = test_scenario::begin(CREATOR);
+
+        suimarines::init_for_testing(test_scenario::ctx(&mut scenario));
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        assert!(
+            test_scenario::has_most_recent_shared<Collection<SUIMARINES>>(),
+            0,
+        );
+        assert!(
+            test_scenario::has_most_recent_for_sender<MintCap<SUIMARINES>>(
+                &scenario,
+            ),
+            0,
+        );
+
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    fun it_calculates_royalty() {
+        let scenario = test_scenario::begin(CREATOR);
+
+        suimarines::init_for_testing(test_scenario::ctx(&mut scenario));
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        let collection = test_scenario::take_shared<Collection<SUIMARINES>>(
+            &scenario,
+        );
+
+        let domain = royalty::royalty_domain(&collection);
+        assert!(
+            royalty::calculate_proportional_royalty(domain, 10000) == 100,
+            0,
+        );
+
+        test_scenario::return_shared(collection);
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    fun it_mints_nft() {
+        let scenario = test_scenario::begin(CREATOR);
+
+        suimarines::init_for_testing(test_scenario::ctx(&mut scenario));
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        let mint_cap = test_scenario::take_from_sender<MintCap<SUIMARINES>>(
+            &scenario,
+        );
+        let inventory = inventory::new(test_scenario::ctx(&mut scenario));
+
+        suimarines::mint_nft(
+            string::utf8(b"Nft"),
+            string::utf8(b"Description of the NFT"),
+            b"https://originbyte.io/",
+            std::vector::singleton(string::utf8(b"rarity")),
+            std::vector::singleton(string::utf8(b"common")),
+            &mint_cap,
+            &mut inventory,
+            test_scenario::ctx(&mut scenario),
+        );
+
+        assert!(!inventory::is_empty(&inventory), 0);
+        transfer::transfer(inventory, CREATOR);
+
+        test_scenario::return_to_sender(&scenario, mint_cap);
+
+        test_scenario::end(scenario);
+    }
+}
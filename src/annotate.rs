@@ -0,0 +1,205 @@
+//! Module annotating serialized configurations with comments describing
+//! their fields, such that configuration files can be edited by hand
+//! without consulting the source code.
+//!
+//! The allowed values of enum fields are derived from the `Deserialize`
+//! implementations of the Rust types, and therefore stay in sync with the
+//! values accepted by `Schema`.
+use crate::sanitize::Sanitization;
+use crate::types::{FieldType, NftTags, NftType, Transferability};
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+use std::collections::HashSet;
+
+/// Returns the descriptions of the fields at the given path, and the
+/// allowed values of enum fields
+fn describe(path: &str) -> Option<(&'static str, &'static [&'static str])> {
+    let description = match path {
+        "Version" => "Version of the configuration layout",
+        "Requires" => "Version ranges of the components the config supports",
+        "Requires.gutenberg" => "Compatible Gutenberg versions, e.g. >=0.2",
+        "Requires.nft_protocol" => "Compatible NftProtocol versions",
+        "NftType" => {
+            return Some(("Type of the NFTs", variants::<NftType>()));
+        }
+        "Collection" => "Metadata of the collection",
+        "Collection.name" => "Name of the collection",
+        "Collection.description" => "Description of the collection",
+        "Collection.symbol" => "Symbol or ticker of the collection",
+        "Collection.tags" => {
+            "Categories of the collection, such as Art or Music, or custom tags"
+        }
+        "Collection.royalty_fee_bps" => "Royalty such as 250bps or 2.5%",
+        "Collection.url" => "http(s) or ipfs URL of the collection website",
+        "Collection.cover_url" => "URL of the collection cover image",
+        "Collection.banner_url" => "URL of the collection banner image",
+        "Collection.module_name" => {
+            "Name of the generated Move module, derived from the name if unset"
+        }
+        "Collection.mutable" => "Collection fields updatable after deployment",
+        "Collection.mutable.description" => "Allow updating the description",
+        "Collection.mutable.url" => "Allow updating the URL",
+        "Collection.mutable.royalty_fee_bps" => "Allow updating the royalty",
+        "Transferability" => {
+            return Some((
+                "Whether NFTs can change hands",
+                variants::<Transferability>(),
+            ));
+        }
+        "NftTags" => {
+            return Some(("Whether NFTs carry tags", variants::<NftTags>()));
+        }
+        "Burnable" => "Generates a burn_nft entry function",
+        "Burnable.redemption_event" => "Emit a RedeemEvent when burning",
+        "Fields" => "Custom typed fields stored on every NFT",
+        "Fields.name" => "Lower snake case name of the field",
+        "Fields.type" => {
+            return Some(("Move type of the field", variants::<FieldType>()));
+        }
+        "Fields.mutable" => "Generate update functions for the field",
+        "Events" => "Events emitted by the generated module",
+        "Events.mint" => "Emit a MintEvent when minting",
+        "Events.burn" => "Emit a BurnEvent when burning, requires Burnable",
+        "Events.field_update" => "Emit a FieldUpdateEvent on field updates",
+        "Events.mint.fields"
+        | "Events.burn.fields"
+        | "Events.field_update.fields" => "Custom fields included in the event",
+        "Marketplace" => "Creates a marketplace with the collection",
+        "Marketplace.admin" | "Listings.admin" => {
+            "Administrator address, defaults to the publisher"
+        }
+        "Marketplace.receiver" | "Listings.receiver" => {
+            "Address receiving proceeds, defaults to the publisher"
+        }
+        "Marketplace.fee_bps" => "Commission on sales in basis points",
+        "Listings" => "Listings selling the NFTs",
+        "Listings.start" => "ISO-8601 time from which sales may open",
+        "Listings.end" => "ISO-8601 time after which sales may not open",
+        "Listings.shared_inventory" => "Markets sell from a single inventory",
+        "Listings.markets" => {
+            "Markets of the listing, !FixedPrice or !DutchAuction"
+        }
+        "Listings.markets.token" => {
+            "Accepted token type, defaults to sui::sui::SUI"
+        }
+        "Listings.markets.price" => "Fixed price in the smallest token unit",
+        "Listings.markets.reserve_price" => {
+            "Reserve price in the smallest token unit"
+        }
+        "Listings.markets.is_whitelisted" => "Only whitelisted buyers may buy",
+        "Listings.markets.fixed_price_after" => {
+            "ISO-8601 time after which an auction may sell at its reserve price"
+        }
+        "OpenEdition" => "Sells NFTs at a fixed price within a mint window",
+        "OpenEdition.receiver" => {
+            "Address receiving payments, defaults to the publisher"
+        }
+        "OpenEdition.token" => "Accepted token type, defaults to sui::sui::SUI",
+        "OpenEdition.price" => "Price in the smallest token unit",
+        "OpenEdition.url" => "URL of the image of the edition",
+        "OpenEdition.start" => "ISO-8601 time from which NFTs may be minted",
+        "OpenEdition.end" => "ISO-8601 time after which minting closes",
+        "Sanitization" => {
+            return Some((
+                "Handling of strings requiring sanitization",
+                variants::<Sanitization>(),
+            ));
+        }
+        "IpfsGateway" => "Gateway to which ipfs:// URLs are rewritten",
+        _ => return None,
+    };
+
+    Some((description, &[]))
+}
+
+/// Annotates a YAML configuration as serialized by `serde_yaml` with a
+/// comment describing each field on its first occurrence
+pub fn annotate(yaml: &str) -> String {
+    let mut annotated = String::new();
+    // Keys of the mappings enclosing the current line and their indentation
+    let mut parents: Vec<(usize, &str)> = Vec::new();
+    let mut described = HashSet::new();
+
+    for line in yaml.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let entry = line.trim_start();
+        let (key_indent, entry) = match entry.strip_prefix("- ") {
+            Some(entry) => (indent + 2, entry),
+            None => (indent, entry),
+        };
+
+        if let Some((key, _)) = entry.split_once(':') {
+            if !key.is_empty() && !key.contains([' ', '!', '"', '\'']) {
+                parents
+                    .retain(|(parent_indent, _)| *parent_indent < key_indent);
+
+                let path = parents
+                    .iter()
+                    .map(|(_, parent)| *parent)
+                    .chain([key])
+                    .collect::<Vec<_>>()
+                    .join(".");
+
+                if let Some((description, values)) = describe(&path) {
+                    if described.insert(path) {
+                        annotated.push_str(&" ".repeat(indent));
+                        annotated.push_str("# ");
+                        annotated.push_str(description);
+                        if !values.is_empty() {
+                            annotated.push_str(", one of: ");
+                            annotated.push_str(&values.join(", "));
+                        }
+                        annotated.push('\n');
+                    }
+                }
+
+                parents.push((key_indent, key));
+            }
+        }
+
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+
+    annotated
+}
+
+/// Names of the variants of an enum as accepted by its `Deserialize`
+/// implementation
+fn variants<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut capture = VariantCapture(&[]);
+    // Deserialization always fails after the variants were captured
+    let _ = T::deserialize(&mut capture);
+    capture.0
+}
+
+/// Deserializer capturing the variant names passed to `deserialize_enum`
+struct VariantCapture(&'static [&'static str]);
+
+impl<'de> Deserializer<'de> for &mut VariantCapture {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("expected an enum"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = variants;
+        Err(de::Error::custom("variants captured"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
@@ -0,0 +1,258 @@
+//! Module containing a builder of `Schema`, such that services can generate
+//! collections programmatically without writing a configuration file.
+//!
+//! The fields which are required by the configuration file are also required
+//! by the builder, and the built schema is sanitized and validated like a
+//! parsed one.
+use crate::err::GutenError;
+use crate::migrations;
+use crate::requirements::Requirements;
+use crate::sanitize::{Modification, Sanitization};
+use crate::schema::{Collection, Schema};
+use crate::types::{
+    Burnable, CollectionMutability, Events, Field, Listing, Marketplace,
+    NftTags, NftType, OpenEdition, Tag, Transferability,
+};
+
+/// Builds a `Schema`, see `Schema::builder`
+#[derive(Debug, Clone)]
+pub struct SchemaBuilder {
+    name: Option<Box<str>>,
+    description: Option<Box<str>>,
+    symbol: Option<Box<str>>,
+    tags: Vec<Tag>,
+    royalty_fee_bps: Option<Box<str>>,
+    url: Option<Box<str>>,
+    cover_url: Option<Box<str>>,
+    banner_url: Option<Box<str>>,
+    module_name: Option<Box<str>>,
+    mutable: CollectionMutability,
+    requires: Requirements,
+    nft_type: NftType,
+    transferability: Transferability,
+    nft_tags: NftTags,
+    burnable: Option<Burnable>,
+    fields: Vec<Field>,
+    events: Events,
+    marketplace: Option<Marketplace>,
+    listings: Option<Vec<Listing>>,
+    open_edition: Option<OpenEdition>,
+    sanitization: Sanitization,
+    ipfs_gateway: Option<String>,
+}
+
+impl Default for SchemaBuilder {
+    fn default() -> Self {
+        SchemaBuilder {
+            name: None,
+            description: None,
+            symbol: None,
+            tags: Vec::new(),
+            royalty_fee_bps: None,
+            url: None,
+            cover_url: None,
+            banner_url: None,
+            module_name: None,
+            mutable: CollectionMutability::default(),
+            requires: Requirements::default(),
+            nft_type: NftType::Classic,
+            transferability: Transferability::default(),
+            nft_tags: NftTags::default(),
+            burnable: None,
+            fields: Vec::new(),
+            events: Events::default(),
+            marketplace: None,
+            listings: None,
+            open_edition: None,
+            sanitization: Sanitization::default(),
+            ipfs_gateway: None,
+        }
+    }
+}
+
+impl Schema {
+    /// Returns a builder of a schema, such as
+    /// `Schema::builder().name("Suimarines").royalties("2.5%")`
+    pub fn builder() -> SchemaBuilder {
+        SchemaBuilder::default()
+    }
+}
+
+impl SchemaBuilder {
+    /// Sets the name of the collection
+    pub fn name(mut self, name: impl Into<Box<str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the description of the collection
+    pub fn description(mut self, description: impl Into<Box<str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the symbol of the collection
+    pub fn symbol(mut self, symbol: impl Into<Box<str>>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Adds a tag to the collection
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.tags.push(tag);
+        self
+    }
+
+    /// Sets the royalty, in the formats accepted by `royalty_fee_bps`
+    pub fn royalties(mut self, royalties: impl Into<Box<str>>) -> Self {
+        self.royalty_fee_bps = Some(royalties.into());
+        self
+    }
+
+    /// Sets the URL of the collection website
+    pub fn url(mut self, url: impl Into<Box<str>>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the URL of the collection cover image
+    pub fn cover_url(mut self, url: impl Into<Box<str>>) -> Self {
+        self.cover_url = Some(url.into());
+        self
+    }
+
+    /// Sets the URL of the collection banner image
+    pub fn banner_url(mut self, url: impl Into<Box<str>>) -> Self {
+        self.banner_url = Some(url.into());
+        self
+    }
+
+    /// Sets the name of the generated Move module
+    pub fn module_name(mut self, module_name: impl Into<Box<str>>) -> Self {
+        self.module_name = Some(module_name.into());
+        self
+    }
+
+    /// Sets the collection fields which can be updated after deployment
+    pub fn mutable(mut self, mutable: CollectionMutability) -> Self {
+        self.mutable = mutable;
+        self
+    }
+
+    /// Sets the versions the schema is compatible with
+    pub fn requires(mut self, requires: Requirements) -> Self {
+        self.requires = requires;
+        self
+    }
+
+    /// Sets the type of the NFTs
+    pub fn nft_type(mut self, nft_type: NftType) -> Self {
+        self.nft_type = nft_type;
+        self
+    }
+
+    /// Sets whether NFTs can change hands
+    pub fn transferability(mut self, transferability: Transferability) -> Self {
+        self.transferability = transferability;
+        self
+    }
+
+    /// Sets whether NFTs carry tags
+    pub fn nft_tags(mut self, nft_tags: NftTags) -> Self {
+        self.nft_tags = nft_tags;
+        self
+    }
+
+    /// Allows NFT owners to burn their NFTs
+    pub fn burnable(mut self, burnable: Burnable) -> Self {
+        self.burnable = Some(burnable);
+        self
+    }
+
+    /// Adds a custom field stored on every NFT
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Sets the events emitted by the generated module
+    pub fn events(mut self, events: Events) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Creates a marketplace with the collection
+    pub fn marketplace(mut self, marketplace: Marketplace) -> Self {
+        self.marketplace = Some(marketplace);
+        self
+    }
+
+    /// Adds a listing selling the NFTs
+    pub fn listing(mut self, listing: Listing) -> Self {
+        self.listings.get_or_insert_with(Vec::new).push(listing);
+        self
+    }
+
+    /// Sells NFTs through an open edition
+    pub fn open_edition(mut self, open_edition: OpenEdition) -> Self {
+        self.open_edition = Some(open_edition);
+        self
+    }
+
+    /// Sets the policy for strings requiring sanitization
+    pub fn sanitization(mut self, sanitization: Sanitization) -> Self {
+        self.sanitization = sanitization;
+        self
+    }
+
+    /// Sets the gateway to which `ipfs://` URLs are rewritten
+    pub fn ipfs_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = Some(gateway.into());
+        self
+    }
+
+    /// Builds the schema and prepares it like a parsed configuration,
+    /// returning the modifications made by sanitization, see
+    /// `Schema::prepare`
+    pub fn build(self) -> Result<(Schema, Vec<Modification>), GutenError> {
+        let mut schema = Schema {
+            version: migrations::CURRENT_VERSION,
+            requires: self.requires,
+            collection: Collection {
+                name: required(self.name, "name")?,
+                description: required(self.description, "description")?,
+                symbol: required(self.symbol, "symbol")?,
+                tags: self.tags,
+                royalty_fee_bps: required(self.royalty_fee_bps, "royalties")?,
+                url: required(self.url, "url")?,
+                cover_url: self.cover_url,
+                banner_url: self.banner_url,
+                module_name: self.module_name,
+                mutable: self.mutable,
+            },
+            nft_type: self.nft_type,
+            transferability: self.transferability,
+            nft_tags: self.nft_tags,
+            burnable: self.burnable,
+            fields: self.fields,
+            events: self.events,
+            marketplace: self.marketplace,
+            listings: self.listings,
+            open_edition: self.open_edition,
+            sanitization: self.sanitization,
+            ipfs_gateway: self.ipfs_gateway,
+        };
+
+        let modifications = schema.prepare()?;
+        Ok((schema, modifications))
+    }
+}
+
+fn required(
+    value: Option<Box<str>>,
+    field: &str,
+) -> Result<Box<str>, GutenError> {
+    value.ok_or_else(|| {
+        GutenError::InvalidConfig(format!("Collection {field} must be set"))
+    })
+}
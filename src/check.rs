@@ -0,0 +1,326 @@
+//! Module containing the compile-verification of generated Move packages.
+//!
+//! The package is built by shelling out to `sui move build`, and the
+//! compiler diagnostics are mapped back to the `Schema` fields rendered into
+//! the offending lines of generated code, such that creators know which part
+//! of their configuration to fix. Common compiler errors are accompanied by
+//! a suggested fix.
+use crate::err::GutenError;
+use crate::schema::Schema;
+use crate::template;
+
+use serde::Serialize;
+use serde_json::Value;
+use tera::{Context, Tera};
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Compiler diagnostic traced back to the configuration
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    /// Compiler message, such as `error[E03002]: unbound module`
+    pub message: String,
+    /// Location of the offending code, if reported by the compiler
+    pub location: Option<(PathBuf, usize)>,
+    /// Offending line of generated code
+    pub code: Option<String>,
+    /// Configuration fields rendered into the offending code
+    pub fields: Vec<String>,
+    /// Suggested fix for common compiler errors
+    pub suggestion: Option<&'static str>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        if let Some((file, line)) = &self.location {
+            write!(f, "\n  at {}:{line}", file.display())?;
+        }
+
+        if let Some(code) = &self.code {
+            write!(f, "\n  | {}", code.trim())?;
+        }
+
+        if !self.fields.is_empty() {
+            write!(f, "\n  related to {}", self.fields.join(", "))?;
+        }
+
+        if let Some(suggestion) = self.suggestion {
+            write!(f, "\n  help: {suggestion}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds the Move package generated from the schema with the given
+/// templates, returns the compiler errors traced back to the schema or an
+/// empty list if the build succeeds
+pub fn check(
+    schema: &Schema,
+    templates: &Tera,
+    package: &Path,
+) -> Result<Vec<Diagnostic>, GutenError> {
+    tracing::debug!("Running sui move build --path {}", package.display());
+    let output = Command::new("sui")
+        .args(["move", "build", "--path"])
+        .arg(package)
+        .output()
+        .map_err(GutenError::SuiCli)?;
+
+    tracing::trace!(
+        "sui move build exited with {}\nstdout:\n{}\nstderr:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    if output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let output = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut diagnostics = parse(&output, package);
+    if diagnostics.is_empty() {
+        // Build failed without any parseable errors, such as when
+        // dependencies cannot be fetched
+        diagnostics.push(Diagnostic {
+            message: output.trim().to_string(),
+            location: None,
+            code: None,
+            fields: Vec::new(),
+            suggestion: Some(
+                "check that the dependencies in Move.toml can be fetched",
+            ),
+        });
+    }
+
+    let module = schema.module_name();
+    let mut traces = HashMap::new();
+    for diagnostic in diagnostics.iter_mut() {
+        // Only the generated files can be traced back to the schema
+        let file = diagnostic
+            .location
+            .as_ref()
+            .and_then(|(file, line)| Some((file.file_stem()?.to_str()?, line)));
+        let (template, line) = match file {
+            Some((file, line)) if *file == *module => (template::MODULE, line),
+            Some((file, line))
+                if file.strip_suffix("_tests") == Some(&*module) =>
+            {
+                (template::TESTS, line)
+            }
+            _ => continue,
+        };
+
+        let fields = match traces.entry(template) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(trace(schema, templates, template)?)
+            }
+        };
+
+        diagnostic.fields = fields
+            .iter()
+            .filter(|(_, _, lines)| lines.contains(line))
+            .map(|(field, value, _)| format!("{field} = {value}"))
+            .collect();
+    }
+
+    for diagnostic in diagnostics.iter_mut() {
+        if diagnostic.suggestion.is_none() {
+            diagnostic.suggestion = suggest(diagnostic);
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+/// Lists the configuration fields alongside their values and the lines of
+/// the template output which render them.
+///
+/// Lines are found by rendering the template again with the field's value
+/// replaced, rather than by searching for the value, such that short values
+/// like `100` are not traced to unrelated lines.
+fn trace(
+    schema: &Schema,
+    templates: &Tera,
+    template: &str,
+) -> Result<Vec<(String, String, Vec<usize>)>, GutenError> {
+    let context = schema.context();
+    let output = templates.render(template, &context)?;
+    let context = context.into_json();
+
+    let mut fields = Vec::new();
+    for (field, value) in schema.field_values() {
+        let mut replaced = context.clone();
+        let rendered = match replaced.pointer_mut(&pointer(&field)) {
+            Some(Value::String(value)) => {
+                value.push_str("_gutenberg_trace");
+                true
+            }
+            Some(Value::Number(number)) => match number.as_u64() {
+                Some(value) => {
+                    *number = (value ^ 1).into();
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        };
+
+        let lines = if rendered {
+            let replaced = Context::from_value(replaced)?;
+            changed_lines(&output, &templates.render(template, &replaced)?)
+        } else {
+            Vec::new()
+        };
+
+        fields.push((field, value, lines));
+    }
+
+    Ok(fields)
+}
+
+/// Pointer to the value of the configuration field in the template context,
+/// such that `Listings[0].markets[1].price` is `/listings/0/markets/1/price`
+fn pointer(field: &str) -> String {
+    let (section, path) = field.split_once('.').unwrap_or((field, ""));
+    let section = match section {
+        "Collection" => String::new(),
+        "Marketplace" => "/marketplace".to_string(),
+        "OpenEdition" => "/open_edition".to_string(),
+        section => format!("/{}", section.to_lowercase()),
+    };
+
+    format!("{section}/{path}")
+        .replace(['.', '['], "/")
+        .replace(']', "")
+}
+
+/// One-based numbers of the lines of the output which are changed or
+/// removed in the replaced output, such that replaced values spanning a
+/// different number of lines do not shift the following lines
+fn changed_lines(output: &str, replaced: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut line = 0;
+
+    for change in ::diff::lines(output, replaced) {
+        match change {
+            ::diff::Result::Left(_) => {
+                line += 1;
+                lines.push(line);
+            }
+            ::diff::Result::Both(_, _) => line += 1,
+            ::diff::Result::Right(_) => {}
+        }
+    }
+
+    lines
+}
+
+/// Suggests a fix for common compiler errors based on their error code and
+/// the configuration fields they were traced back to
+fn suggest(diagnostic: &Diagnostic) -> Option<&'static str> {
+    let code = diagnostic
+        .message
+        .strip_prefix("error[")?
+        .split_once(']')?
+        .0;
+    let is_token = diagnostic.fields.iter().any(|f| f.contains(".token = "));
+
+    let suggestion = match code {
+        "E03002" | "E03003" | "E03004" if is_token => {
+            "the market token type could not be found, check that its module is published and added as a dependency in Move.toml"
+        }
+        "E03002" | "E03003" | "E03004" => {
+            "the generated code references a module or member missing from its dependencies, check that the template directory supports the NftProtocol version in Move.toml and declare compatible versions under Requires"
+        }
+        "E02001" => {
+            "an identifier is declared twice, rename the custom field or the function of extensions.move colliding with the generated code"
+        }
+        code if code.starts_with("E01") => {
+            "the generated code is not valid Move syntax, check the related fields for characters which must be escaped or identifiers which are reserved words"
+        }
+        code if code.starts_with("E04") || code.starts_with("E05") => {
+            "the generated code does not type check, check that custom templates match the data of the configuration"
+        }
+        _ => return None,
+    };
+
+    Some(suggestion)
+}
+
+/// Parses compiler diagnostics of the form
+///
+/// ```text
+/// error[E03002]: unbound module
+///    ┌─ ./sources/suitraders.move:85:9
+/// ```
+fn parse(output: &str, package: &Path) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    for line in output.lines() {
+        if line.starts_with("error") {
+            diagnostics.push(Diagnostic {
+                message: line.to_string(),
+                location: None,
+                code: None,
+                fields: Vec::new(),
+                suggestion: None,
+            });
+            continue;
+        }
+
+        let (Some(diagnostic), Some(location)) =
+            (diagnostics.last_mut(), line.trim().strip_prefix("┌─ "))
+        else {
+            continue;
+        };
+
+        if diagnostic.location.is_some() {
+            continue;
+        }
+
+        let mut parts = location.rsplitn(3, ':');
+        let (Some(_column), Some(line), Some(file)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let Ok(line) = line.parse::<usize>() else {
+            continue;
+        };
+
+        let file = resolve(file, package);
+        diagnostic.code = fs::read_to_string(&file).ok().and_then(|source| {
+            source
+                .lines()
+                .nth(line.saturating_sub(1))
+                .map(str::to_string)
+        });
+        diagnostic.location = Some((file, line));
+    }
+
+    diagnostics
+}
+
+/// Resolves paths reported relative to the package directory
+fn resolve(file: &str, package: &Path) -> PathBuf {
+    let path = PathBuf::from(file);
+    if path.is_relative() && !path.exists() {
+        package.join(path)
+    } else {
+        path
+    }
+}
@@ -0,0 +1,96 @@
+//! Module containing the constraints that platforms embedding Gutenberg can
+//! impose on configurations, such that every generated contract meets their
+//! policy.
+//!
+//! Constraints are loaded from a separate YAML file and enforced in addition
+//! to `Schema::validate`.
+use crate::err::GutenError;
+use crate::schema::Schema;
+use crate::types::{Listing, MarketKind, Tag, Transferability};
+
+use serde::Deserialize;
+
+/// Policy limits enforced on configurations
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Constraints {
+    /// Maximum royalty in basis points
+    pub max_royalty_fee_bps: Option<u64>,
+    /// Market types which listings may use, any market type is allowed if
+    /// not set
+    pub allowed_markets: Option<Vec<MarketKind>>,
+    /// Collections carrying any of these tags must not be `Transferable`
+    #[serde(default)]
+    pub soulbound_tags: Vec<Tag>,
+}
+
+impl Constraints {
+    /// Parses constraints from YAML
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Constraints, GutenError> {
+        Ok(serde_yaml::from_reader(reader)?)
+    }
+
+    /// Checks the schema against the constraints, reporting every violation
+    pub fn enforce(&self, schema: &Schema) -> Result<(), GutenError> {
+        tracing::debug!("Enforcing platform constraints");
+        let mut violations = Vec::new();
+
+        if let Some(max) = self.max_royalty_fee_bps {
+            match schema.collection.royalty_bps() {
+                Ok(royalty) if royalty <= max => (),
+                Ok(royalty) => violations.push(format!(
+                    "Royalty of {royalty} bps exceeds the maximum of {max} bps"
+                )),
+                Err(err) => violations.push(err),
+            }
+        }
+
+        if let Some(allowed) = &self.allowed_markets {
+            let markets =
+                schema.listings.iter().flatten().flat_map(Listing::markets);
+
+            for market in markets {
+                if !allowed.contains(&market.kind()) {
+                    violations.push(format!(
+                        "{:?} markets are not allowed",
+                        market.kind()
+                    ));
+                }
+
+                // Fallbacks open a fixed price venue
+                if market.fixed_price_after().is_some()
+                    && !allowed.contains(&MarketKind::FixedPrice)
+                {
+                    violations.push(
+                        "Auction fallbacks to FixedPrice markets are not allowed"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+
+        if schema.transferability == Transferability::Transferable {
+            let tags = self
+                .soulbound_tags
+                .iter()
+                .filter(|tag| schema.collection.tags.contains(tag));
+
+            for tag in tags {
+                violations.push(format!(
+                    "Collections tagged {tag:?} must not be Transferable"
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(GutenError::InvalidConfig(format!(
+                "Configuration violates constraints: {}",
+                violations.join("; ")
+            )))
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Module containing the structural comparison of configurations.
+//!
+//! Configurations are compared after deserialization into `Schema`, such
+//! that formatting, key order, and omitted defaults do not show up as
+//! changes.
+use crate::err::GutenError;
+use crate::schema::Schema;
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+use std::fmt;
+
+/// Change of a single configuration field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { path, value } => {
+                write!(f, "+ {path}: {}", compact(value))
+            }
+            Change::Removed { path, value } => {
+                write!(f, "- {path}: {}", compact(value))
+            }
+            Change::Changed { path, from, to } => {
+                write!(f, "~ {path}: {} -> {}", compact(from), compact(to))
+            }
+        }
+    }
+}
+
+/// Formats the value on a single line
+fn compact(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("{value:?}"))
+}
+
+/// Lists the field level changes from one configuration to another
+pub fn diff(from: &Schema, to: &Schema) -> Result<Vec<Change>, GutenError> {
+    let mut changes = Vec::new();
+    compare(
+        String::new(),
+        &serde_yaml::to_value(from)?,
+        &serde_yaml::to_value(to)?,
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+fn compare(path: String, from: &Value, to: &Value, changes: &mut Vec<Change>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match (from, to) {
+        (Value::Mapping(from), Value::Mapping(to)) => {
+            for (key, from_value) in from {
+                let key_path = child(&key_name(key));
+                match to.get(key) {
+                    Some(to_value) => {
+                        compare(key_path, from_value, to_value, changes)
+                    }
+                    None => changes.push(Change::Removed {
+                        path: key_path,
+                        value: from_value.clone(),
+                    }),
+                }
+            }
+
+            for (key, to_value) in to {
+                if !from.contains_key(key) {
+                    changes.push(Change::Added {
+                        path: child(&key_name(key)),
+                        value: to_value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Sequence(from), Value::Sequence(to)) => {
+            for index in 0..from.len().max(to.len()) {
+                let index_path = format!("{path}[{index}]");
+                match (from.get(index), to.get(index)) {
+                    (Some(from), Some(to)) => {
+                        compare(index_path, from, to, changes)
+                    }
+                    (Some(from), None) => changes.push(Change::Removed {
+                        path: index_path,
+                        value: from.clone(),
+                    }),
+                    (None, Some(to)) => changes.push(Change::Added {
+                        path: index_path,
+                        value: to.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Tagged(from), Value::Tagged(to)) if from.tag == to.tag => {
+            compare(path, &from.value, &to.value, changes)
+        }
+        // Absent optional sections are serialized as null
+        (Value::Null, to) if !to.is_null() => changes.push(Change::Added {
+            path,
+            value: to.clone(),
+        }),
+        (from, Value::Null) if !from.is_null() => {
+            changes.push(Change::Removed {
+                path,
+                value: from.clone(),
+            })
+        }
+        (from, to) if from != to => changes.push(Change::Changed {
+            path,
+            from: from.clone(),
+            to: to.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn key_name(key: &Value) -> String {
+    key.as_str().map_or_else(|| compact(key), str::to_string)
+}
@@ -1,9 +1,69 @@
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum GutenError {
-    #[error("Parsing error has occured")]
+    #[error("Parsing error has occured: {0}")]
     SerdeYaml(#[from] serde_yaml::Error),
-    #[error("An IO error has occured")]
+    #[error("Parsing error has occured: {0}")]
+    TomlDe(#[from] toml::de::Error),
+    #[error("Serialization error has occured: {0}")]
+    TomlSer(#[from] toml::ser::Error),
+    #[error("An IO error has occured: {0}")]
     IoError(#[from] std::io::Error),
+    #[error("Template error has occured: {0}")]
+    Template(#[from] tera::Error),
+    #[error("Could not run the Sui CLI: {0}")]
+    SuiCli(std::io::Error),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Incompatible versions: {0}")]
+    Incompatible(String),
+    #[error("Generated package failed to compile with {0} errors")]
+    Compilation(usize),
+}
+
+impl GutenError {
+    /// Returns the category of the error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            GutenError::SerdeYaml(_)
+            | GutenError::TomlDe(_)
+            | GutenError::InvalidConfig(_)
+            | GutenError::Incompatible(_) => ErrorKind::Validation,
+            GutenError::IoError(_) | GutenError::TomlSer(_) => ErrorKind::Io,
+            GutenError::Template(_) => ErrorKind::Template,
+            GutenError::SuiCli(_) => ErrorKind::Toolchain,
+            GutenError::Compilation(_) => ErrorKind::Compilation,
+        }
+    }
+}
+
+/// Category of a `GutenError`, such that callers can branch on the class of
+/// failure without matching every error variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The configuration could not be parsed or is invalid
+    Validation,
+    /// Reading or writing files failed
+    Io,
+    /// A template failed to render
+    Template,
+    /// An external tool such as the Sui CLI could not be run
+    Toolchain,
+    /// The generated package failed to compile
+    Compilation,
+}
+
+impl ErrorKind {
+    /// Exit code of the CLI when failing with an error of this kind
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Io | ErrorKind::Template => 1,
+            ErrorKind::Validation => 2,
+            ErrorKind::Toolchain => 3,
+            ErrorKind::Compilation => 4,
+        }
+    }
 }
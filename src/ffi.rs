@@ -0,0 +1,114 @@
+//! Module exposing the generator through a C ABI, such that tooling written
+//! in other languages can validate configurations and generate Move code
+//! without running the CLI.
+//!
+//! Every function takes a configuration as a nul terminated UTF-8 string and
+//! returns a JSON document in the layout printed by the `--json` flag of the
+//! CLI, which the caller must release with `gutenberg_free`. Panics are
+//! caught and reported as `{"error": ...}` without a `kind`.
+use crate::err::GutenError;
+use crate::sanitize::Modification;
+use crate::schema::Schema;
+
+use serde_json::{json, Value};
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Sanitizes and validates the configuration, returns the values modified
+/// by sanitization as `{"modifications": [...]}` or `{"error": ..., "kind": ...}`.
+///
+/// # Safety
+///
+/// `config` must be null or point to a nul terminated string, and the
+/// returned string must be released with `gutenberg_free`.
+#[no_mangle]
+pub unsafe extern "C" fn gutenberg_validate(
+    config: *const c_char,
+) -> *mut c_char {
+    respond(config, |_, modifications| {
+        Ok(json!({ "modifications": modifications }))
+    })
+}
+
+/// Generates the Move module and tests of the configuration, returns
+/// `{"module_name": ..., "module": ..., "tests": ...}` or
+/// `{"error": ..., "kind": ...}`.
+///
+/// # Safety
+///
+/// `config` must be null or point to a nul terminated string, and the
+/// returned string must be released with `gutenberg_free`.
+#[no_mangle]
+pub unsafe extern "C" fn gutenberg_generate(
+    config: *const c_char,
+) -> *mut c_char {
+    respond(config, |schema, _| {
+        let mut module = Vec::new();
+        schema.write_move(&mut module)?;
+        let mut tests = Vec::new();
+        schema.write_move_tests(&mut tests)?;
+
+        Ok(json!({
+            "module_name": schema.module_name(),
+            "module": String::from_utf8_lossy(&module),
+            "tests": String::from_utf8_lossy(&tests),
+        }))
+    })
+}
+
+/// Releases a string returned by this module.
+///
+/// # Safety
+///
+/// `string` must be null or have been returned by a function of this module,
+/// and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn gutenberg_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Parses, sanitizes, and validates the configuration, and serializes the
+/// result of `f` or the error as JSON, catching panics as they must not
+/// unwind into the caller
+unsafe fn respond(
+    config: *const c_char,
+    f: impl FnOnce(&Schema, Vec<Modification>) -> Result<Value, GutenError>,
+) -> *mut c_char {
+    let response = panic::catch_unwind(AssertUnwindSafe(|| {
+        let result = if config.is_null() {
+            Err(GutenError::InvalidConfig(
+                "Configuration must not be null".to_string(),
+            ))
+        } else {
+            CStr::from_ptr(config)
+                .to_str()
+                .map_err(|err| GutenError::InvalidConfig(err.to_string()))
+                .and_then(|config| {
+                    let mut schema = Schema::from_reader(config.as_bytes())?;
+                    let modifications = schema.prepare()?;
+                    f(&schema, modifications)
+                })
+        };
+
+        match result {
+            Ok(response) => response,
+            Err(err) => json!({ "error": err.to_string(), "kind": err.kind() }),
+        }
+    }))
+    .unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string());
+        json!({ "error": format!("Gutenberg panicked: {message}") })
+    });
+
+    // Serialized JSON escapes nul characters
+    CString::new(response.to_string())
+        .expect("JSON must not contain nul characters")
+        .into_raw()
+}
@@ -0,0 +1,167 @@
+//! Module containing the file formats in which configurations can be
+//! written.
+//!
+//! Configurations in every format are read into the same untyped YAML
+//! document, such that migrations and deserialization into `Schema` are
+//! shared between formats.
+use crate::err::GutenError;
+
+use serde_yaml::value::{Tag, TaggedValue};
+use serde_yaml::Value;
+
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::Path;
+
+/// File format of a configuration
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// YAML, which also accepts JSON configurations
+    #[default]
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    /// Detects the format of the configuration from its file extension,
+    /// defaulting to YAML
+    pub fn from_path(path: &Path) -> Format {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("toml") => Format::Toml,
+            _ => Format::Yaml,
+        }
+    }
+
+    /// Reads a configuration into an untyped YAML document
+    pub fn read<R: Read>(self, mut reader: R) -> Result<Value, GutenError> {
+        match self {
+            Format::Yaml => Ok(serde_yaml::from_reader(reader)?),
+            Format::Toml => {
+                let mut config = String::new();
+                reader.read_to_string(&mut config)?;
+
+                let mut config = from_toml(toml::from_str(&config)?);
+                tag_markets(&mut config);
+                Ok(config)
+            }
+        }
+    }
+
+    /// Writes an untyped YAML document in the format
+    pub fn write(self, config: &Value) -> Result<String, GutenError> {
+        match self {
+            Format::Yaml => Ok(serde_yaml::to_string(config)?),
+            Format::Toml => {
+                let config = to_toml(config)?.unwrap_or_else(|| {
+                    toml::Value::Table(toml::Table::default())
+                });
+                Ok(toml::to_string_pretty(&config)?)
+            }
+        }
+    }
+}
+
+fn from_toml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(string) => Value::String(string),
+        toml::Value::Integer(integer) => Value::Number(integer.into()),
+        toml::Value::Float(float) => Value::Number(float.into()),
+        toml::Value::Boolean(boolean) => Value::Bool(boolean),
+        // Dates are parsed from ISO-8601 strings by `Schema`
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(array) => {
+            Value::Sequence(array.into_iter().map(from_toml).collect())
+        }
+        toml::Value::Table(table) => Value::Mapping(
+            table
+                .into_iter()
+                .map(|(key, value)| (Value::String(key), from_toml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Converts the document to TOML, returns `None` for null values which TOML
+/// cannot represent and are therefore omitted
+fn to_toml(value: &Value) -> Result<Option<toml::Value>, GutenError> {
+    let value = match value {
+        Value::Null => return Ok(None),
+        Value::Bool(boolean) => toml::Value::Boolean(*boolean),
+        Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(integer), _) => toml::Value::Integer(integer),
+            (None, Some(float)) if !number.is_u64() => {
+                toml::Value::Float(float)
+            }
+            _ => {
+                return Err(GutenError::InvalidConfig(format!(
+                    "Number {number} cannot be represented in TOML"
+                )))
+            }
+        },
+        Value::String(string) => toml::Value::String(string.clone()),
+        Value::Sequence(sequence) => toml::Value::Array(
+            sequence
+                .iter()
+                .map(to_toml)
+                .filter_map(Result::transpose)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Mapping(mapping) => {
+            let mut table = toml::Table::new();
+            for (key, value) in mapping {
+                let Some(key) = key.as_str() else {
+                    return Err(GutenError::InvalidConfig(format!(
+                        "Key {key:?} cannot be represented in TOML"
+                    )));
+                };
+                if let Some(value) = to_toml(value)? {
+                    table.insert(key.to_string(), value);
+                }
+            }
+            toml::Value::Table(table)
+        }
+        // Enum variants are written as a table keyed by the variant name
+        Value::Tagged(tagged) => {
+            let mut table = toml::Table::new();
+            if let Some(value) = to_toml(&tagged.value)? {
+                table.insert(
+                    tagged.tag.to_string().trim_start_matches('!').to_string(),
+                    value,
+                );
+            }
+            toml::Value::Table(table)
+        }
+    };
+
+    Ok(Some(value))
+}
+
+/// Converts the markets of listings, which are written as tables keyed by
+/// the market type in TOML, into the tagged values expected from YAML
+fn tag_markets(config: &mut Value) {
+    let markets = config
+        .get_mut("Listings")
+        .and_then(Value::as_sequence_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(|listing| listing.get_mut("markets"))
+        .filter_map(Value::as_sequence_mut)
+        .flatten();
+
+    for market in markets {
+        let Some((kind, value)) = market
+            .as_mapping()
+            .filter(|market| market.len() == 1)
+            .and_then(|market| market.iter().next())
+        else {
+            continue;
+        };
+
+        if let Some(kind) = kind.as_str() {
+            *market = Value::Tagged(Box::new(TaggedValue {
+                tag: Tag::new(kind),
+                value: value.clone(),
+            }));
+        }
+    }
+}
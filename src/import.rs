@@ -0,0 +1,130 @@
+//! Module importing configurations written for other NFT tooling, such that
+//! collections launched elsewhere can be migrated to Sui.
+//!
+//! Imports produce an untyped configuration document, which is checked to
+//! deserialize into a valid `Schema`, alongside the settings of the source
+//! configuration which have no equivalent in the schema.
+use crate::err::GutenError;
+use crate::migrations;
+use crate::schema::Schema;
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use std::io::Read;
+
+/// Configuration imported from other tooling
+#[derive(Debug, Clone)]
+pub struct Import {
+    /// Imported configuration document
+    pub config: Value,
+    /// Descriptions of the settings which could not be imported
+    pub skipped: Vec<String>,
+}
+
+/// Settings of a Metaplex Candy Machine `config.json`, as written by both
+/// the Candy Machine v2 CLI and Sugar
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CandyMachine {
+    #[serde(default)]
+    symbol: String,
+    #[serde(default)]
+    seller_fee_basis_points: u64,
+    number: Option<u64>,
+    price: Option<f64>,
+    go_live_date: Option<String>,
+    #[serde(default)]
+    creators: Vec<Value>,
+    /// Sugar configures the go-live date as a start date guard
+    guards: Option<Value>,
+}
+
+/// Imports a Candy Machine `config.json` as the configuration of a
+/// collection with the given name.
+///
+/// The symbol, royalty, and go-live date are imported, whereas the
+/// description and URL, which Candy Machine stores in the metadata of each
+/// NFT, are left empty.
+pub fn candy_machine<R: Read>(
+    reader: R,
+    name: &str,
+) -> Result<Import, GutenError> {
+    let candy_machine: CandyMachine = serde_yaml::from_reader(reader)?;
+    let mut skipped = Vec::new();
+
+    let mut collection = Mapping::new();
+    collection.insert("name".into(), name.into());
+    collection.insert("description".into(), "".into());
+    collection.insert("symbol".into(), candy_machine.symbol.into());
+    collection.insert("tags".into(), Value::Sequence(Vec::new()));
+    collection.insert(
+        "royalty_fee_bps".into(),
+        candy_machine.seller_fee_basis_points.to_string().into(),
+    );
+    collection.insert("url".into(), "".into());
+
+    let mut config = Mapping::new();
+    config.insert("Version".into(), migrations::CURRENT_VERSION.into());
+    config.insert("NftType".into(), "Classic".into());
+    config.insert("Collection".into(), Value::Mapping(collection));
+
+    let go_live_date = candy_machine.go_live_date.or_else(|| {
+        candy_machine
+            .guards
+            .as_ref()
+            .and_then(|guards| guards["default"]["startDate"]["date"].as_str())
+            .map(str::to_string)
+    });
+
+    if let Some(date) = go_live_date {
+        let start = parse_date(&date).ok_or_else(|| {
+            GutenError::InvalidConfig(format!(
+                "Candy Machine go-live date {date:?} is not a valid date"
+            ))
+        })?;
+
+        let mut listing = Mapping::new();
+        listing.insert("start".into(), start.into());
+        listing.insert("markets".into(), Value::Sequence(Vec::new()));
+        config.insert(
+            "Listings".into(),
+            Value::Sequence(vec![Value::Mapping(listing)]),
+        );
+    }
+
+    if let Some(price) = candy_machine.price {
+        skipped.push(format!(
+            "price of {price} SOL, add a market denominated in SUI to the listing"
+        ));
+    }
+    if let Some(number) = candy_machine.number {
+        skipped.push(format!("supply of {number} NFTs"));
+    }
+    if !candy_machine.creators.is_empty() {
+        skipped.push(format!(
+            "{} creators, royalties are paid to the publisher",
+            candy_machine.creators.len()
+        ));
+    }
+
+    let config = Value::Mapping(config);
+    let schema: Schema = serde_yaml::from_value(config.clone())?;
+    schema.validate()?;
+
+    Ok(Import { config, skipped })
+}
+
+/// Parses dates in RFC 3339, as written by Sugar, or in the format written
+/// by the Candy Machine v2 CLI such as `25 Dec 2021 00:00:00 GMT`
+fn parse_date(date: &str) -> Option<String> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.to_rfc3339());
+    }
+
+    let date = date.strip_suffix(" GMT").unwrap_or(date);
+    NaiveDateTime::parse_from_str(date, "%d %b %Y %H:%M:%S")
+        .ok()
+        .map(|date| date.and_utc().to_rfc3339())
+}
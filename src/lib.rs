@@ -1,4 +1,23 @@
+pub mod annotate;
+pub mod builder;
+#[cfg(feature = "cli")]
+pub mod check;
+pub mod constraints;
+pub mod diff;
 pub mod err;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
+pub mod import;
+pub mod migrations;
 pub mod prelude;
+pub mod report;
+pub mod requirements;
+pub mod sanitize;
 pub mod schema;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod template;
 pub mod types;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,9 +1,14 @@
 use gutenberg::prelude::*;
 
 use gumdrop::Options;
+use serde_json::json;
+use tracing::Level;
 
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 #[derive(Debug, Options)]
 struct Opt {
@@ -11,24 +16,137 @@ struct Opt {
     config: PathBuf,
     #[options(help = "output file path")]
     output: Option<PathBuf>,
+    #[options(help = "directory of templates replacing the built-in ones")]
+    template_dir: Option<PathBuf>,
+    #[options(no_short, help = "platform constraints file to enforce")]
+    constraints: Option<PathBuf>,
+    #[options(help = "print the changes from the config to another config")]
+    diff: Option<PathBuf>,
+    #[options(help = "compile the generated package with the Sui CLI")]
+    check: bool,
+    #[options(help = "report the generated code size of enabled features")]
+    report: bool,
+    #[options(help = "upgrade the config file to the latest schema version")]
+    migrate: bool,
+    #[options(help = "comment the fields of configs written by --migrate")]
+    annotate: bool,
+    #[options(no_short, help = "print results as JSON")]
+    json: bool,
+    #[options(count, help = "log progress, -vv also logs Sui CLI output")]
+    verbose: u32,
+    #[options(no_short, help = "write the most verbose logs to a file")]
+    log_file: Option<PathBuf>,
+    #[options(no_short, help = "write the config from a Candy Machine config")]
+    import_candy_machine: Option<PathBuf>,
+    #[options(no_short, help = "serve the generator over HTTP on the address")]
+    serve: Option<SocketAddr>,
     #[options(help = "print help message")]
     help: bool,
 }
 
-fn main() -> Result<(), GutenError> {
+fn main() {
     let opt = Opt::parse_args_default_or_exit();
+    let json = opt.json;
 
-    let f = fs::File::open(opt.config)?;
+    if let Err(err) = init_logging(opt.verbose, opt.log_file.as_deref()) {
+        fail(json, "Could not open log file", &err);
+    }
 
-    let schema: Schema = match serde_yaml::from_reader(f) {
-        Ok(schema) => schema,
-        Err(err) => {
-            eprintln!("Gutenberg could not generate smart contract due to");
-            eprintln!("{}", err);
-            std::process::exit(2);
-        }
+    match run(opt) {
+        Ok(()) => {}
+        // Output piped into a closed reader, such as `head`
+        Err(GutenError::IoError(err))
+            if err.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(err) => fail(json, "Error", &err),
+    }
+}
+
+fn run(opt: Opt) -> Result<(), GutenError> {
+    if let Some(addr) = opt.serve {
+        return serve(addr);
+    }
+
+    if let Some(candy_machine) = &opt.import_candy_machine {
+        return import_candy_machine(&opt.config, candy_machine, opt.json);
+    }
+
+    if opt.migrate {
+        return migrate(&opt.config, opt.annotate, opt.json);
+    }
+
+    let format = Format::from_path(&opt.config);
+    let f = fs::File::open(&opt.config)?;
+    let constraints = match &opt.constraints {
+        Some(path) => Some(Constraints::from_reader(fs::File::open(path)?)?),
+        None => None,
     };
 
+    let (schema, modifications) = match load(format, f, constraints.as_ref()) {
+        Ok(result) => result,
+        Err(err) => fail(
+            opt.json,
+            "Gutenberg could not generate smart contract due to",
+            &err,
+        ),
+    };
+
+    if !opt.json {
+        for modification in &modifications {
+            eprintln!("{modification}");
+        }
+
+        // Preview module names which are not trivially derived from the
+        // collection name
+        let module_name = schema.module_name();
+        if *module_name != schema.collection.name.to_lowercase() {
+            eprintln!(
+                "Collection {:?} will be generated as module gutenberg::{module_name}",
+                schema.collection.name
+            );
+        }
+
+        // Validated by `load`
+        let royalty = schema.collection.royalty_bps().unwrap_or_default();
+        eprintln!(
+            "Royalty of {}.{:02}% of each sale",
+            royalty / 100,
+            royalty % 100
+        );
+    }
+
+    if let Some(other) = &opt.diff {
+        let f = fs::File::open(other)?;
+        let (other_schema, _) =
+            match load(Format::from_path(other), f, constraints.as_ref()) {
+                Ok(result) => result,
+                Err(err) => fail(
+                    opt.json,
+                    &format!("Could not load {} due to", other.display()),
+                    &err,
+                ),
+            };
+
+        let changes = diff::diff(&schema, &other_schema)?;
+        if opt.json {
+            print_json(&changes)?;
+        } else {
+            for change in changes {
+                writeln!(io::stdout(), "{change}")?;
+            }
+        }
+        return Ok(());
+    }
+
+    if opt.report {
+        let report = report::report(&schema)?;
+        if opt.json {
+            print_json(&report)?;
+        } else {
+            writeln!(io::stdout(), "{report}")?;
+        }
+        return Ok(());
+    }
+
     // If output file was not specified we prepare build directory for user to
     // publish directly after invoking gutenberg
     if opt.output.is_none() {
@@ -38,7 +156,7 @@ fn main() -> Result<(), GutenError> {
     }
 
     // Identify final output path and create intermediate directories
-    let output_file = opt.output.unwrap_or_else(|| {
+    let output_file = opt.output.clone().unwrap_or_else(|| {
         PathBuf::from(&format!(
             "./build/sources/{}.move",
             &schema.module_name().to_string()
@@ -49,9 +167,261 @@ fn main() -> Result<(), GutenError> {
         fs::create_dir_all(p)?;
     }
 
-    let mut f = fs::File::create(output_file)?;
-    if let Err(err) = schema.write_move(&mut f) {
-        eprintln!("{err}");
+    let templates = match &opt.template_dir {
+        Some(dir) => template::from_dir(dir)?,
+        None => template::builtin(),
+    };
+
+    let mut f = fs::File::create(&output_file)?;
+    schema.write_move_with(&templates, &mut f)?;
+
+    // Tests are only generated alongside the build directory package
+    let tests_file = opt.output.is_none().then(|| {
+        PathBuf::from(format!(
+            "./build/tests/{}_tests.move",
+            schema.module_name()
+        ))
+    });
+
+    if let Some(tests_file) = &tests_file {
+        fs::create_dir_all("./build/tests")?;
+        let mut f = fs::File::create(tests_file)?;
+        schema.write_move_tests_with(&templates, &mut f)?;
+    }
+
+    let mut result = json!({
+        "module_name": schema.module_name(),
+        "module": output_file,
+        "tests": tests_file,
+        "modifications": modifications,
+    });
+
+    if opt.check {
+        if opt.output.is_some() {
+            return Err(GutenError::InvalidConfig(
+                "Only the ./build package can be checked, omit --output"
+                    .to_string(),
+            ));
+        }
+
+        let diagnostics =
+            check::check(&schema, &templates, Path::new("./build"))?;
+        let failed = (!diagnostics.is_empty())
+            .then(|| GutenError::Compilation(diagnostics.len()));
+
+        if opt.json {
+            result["compiled"] = json!(failed.is_none());
+            result["diagnostics"] = json!(diagnostics);
+        } else if failed.is_none() {
+            println!("Generated package compiled successfully");
+        } else {
+            for diagnostic in diagnostics {
+                eprintln!("{diagnostic}\n");
+            }
+        }
+
+        if let Some(err) = failed {
+            if !opt.json {
+                return Err(err);
+            }
+
+            // Reported alongside the diagnostics instead of by `fail`
+            result["error"] = json!(err.to_string());
+            result["kind"] = json!(err.kind());
+            print_json(&result)?;
+            std::process::exit(err.kind().exit_code());
+        }
+    }
+
+    if opt.json {
+        print_json(&result)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "serve")]
+fn serve(addr: SocketAddr) -> Result<(), GutenError> {
+    tokio::runtime::Runtime::new()?.block_on(gutenberg::serve::serve(addr))
+}
+
+#[cfg(not(feature = "serve"))]
+fn serve(_addr: SocketAddr) -> Result<(), GutenError> {
+    Err(GutenError::InvalidConfig(
+        "Gutenberg was built without the serve feature".to_string(),
+    ))
+}
+
+/// Logs to stderr at the verbosity level, or everything to the log file
+fn init_logging(
+    verbose: u32,
+    log_file: Option<&Path>,
+) -> Result<(), GutenError> {
+    let subscriber =
+        tracing_subscriber::fmt().without_time().with_target(false);
+
+    match log_file {
+        Some(path) => {
+            let file = fs::File::create(path)?;
+            subscriber
+                .with_max_level(Level::TRACE)
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .init();
+        }
+        None => {
+            let level = match verbose {
+                0 => Level::WARN,
+                1 => Level::DEBUG,
+                _ => Level::TRACE,
+            };
+            subscriber
+                .with_max_level(level)
+                .with_writer(io::stderr)
+                .init();
+        }
+    }
+
+    Ok(())
+}
+
+/// Reports the error, as JSON if requested, and exits with the exit code of
+/// its kind
+fn fail(json: bool, context: &str, err: &GutenError) -> ! {
+    if json {
+        println!(
+            "{}",
+            json!({ "error": err.to_string(), "kind": err.kind() })
+        );
+    } else {
+        eprintln!("{context}: {err}");
+    }
+    std::process::exit(err.kind().exit_code())
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), GutenError> {
+    let value = serde_json::to_string_pretty(value)
+        .expect("Results must serialize to JSON");
+    writeln!(io::stdout(), "{value}")?;
+    Ok(())
+}
+
+/// Parses, sanitizes, and validates the config file, enforcing the platform
+/// constraints if provided
+fn load(
+    format: Format,
+    f: fs::File,
+    constraints: Option<&Constraints>,
+) -> Result<(Schema, Vec<Modification>), GutenError> {
+    let mut schema = Schema::from_reader_with(format, f)?;
+    let modifications = schema.prepare()?;
+    if let Some(constraints) = constraints {
+        constraints.enforce(&schema)?;
+    }
+
+    Ok((schema, modifications))
+}
+
+/// Rewrites the config file in the latest schema version, optionally
+/// annotating its fields with comments.
+///
+/// The config is written to a temporary file which replaces the original,
+/// such that an interrupted migration does not leave a truncated config.
+/// Comments of the original config are not kept.
+fn migrate(
+    config: &PathBuf,
+    annotate: bool,
+    json: bool,
+) -> Result<(), GutenError> {
+    let format = Format::from_path(config);
+    let mut value = format.read(fs::File::open(config)?)?;
+
+    if annotate && format != Format::Yaml {
+        return Err(GutenError::InvalidConfig(
+            "Only YAML configs can be annotated".to_string(),
+        ));
+    }
+
+    let migrated = migrations::migrate(&mut value)?;
+    if migrated || annotate {
+        let mut output = format.write(&value)?;
+        if annotate {
+            output = annotate::annotate(&output);
+        }
+
+        let name = config
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let temporary = config.with_file_name(format!(".{name}.migrate"));
+        fs::write(&temporary, output)?;
+        fs::rename(&temporary, config)?;
+    }
+
+    let rewritten = migrated || annotate;
+    if json {
+        print_json(&json!({
+            "config": config,
+            "version": migrations::CURRENT_VERSION,
+            "migrated": migrated,
+            "annotated": annotate,
+            "comments_kept": !rewritten,
+        }))?;
+    } else if migrated {
+        println!(
+            "Migrated {} to schema version {}",
+            config.display(),
+            migrations::CURRENT_VERSION
+        );
+    } else if annotate {
+        println!("Annotated {}", config.display());
+    } else {
+        println!("{} is already up to date", config.display());
+    }
+
+    if rewritten && !json {
+        eprintln!("Comments of the original config were not kept");
+    }
+
+    Ok(())
+}
+
+/// Writes the config imported from a Candy Machine config, named after the
+/// config file
+fn import_candy_machine(
+    config: &Path,
+    candy_machine: &Path,
+    json: bool,
+) -> Result<(), GutenError> {
+    if config.exists() {
+        return Err(GutenError::InvalidConfig(format!(
+            "{} already exists",
+            config.display()
+        )));
+    }
+
+    let name = config
+        .file_stem()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let import = import::candy_machine(fs::File::open(candy_machine)?, &name)?;
+    fs::write(config, Format::from_path(config).write(&import.config)?)?;
+
+    if json {
+        print_json(&json!({
+            "config": config,
+            "skipped": import.skipped,
+        }))?;
+    } else {
+        println!(
+            "Imported {} into {}",
+            candy_machine.display(),
+            config.display()
+        );
+        for skipped in &import.skipped {
+            eprintln!("Skipped the {skipped}");
+        }
+        eprintln!("Fill in the description and url of the collection");
     }
 
     Ok(())
@@ -0,0 +1,73 @@
+//! Module containing the migrations that upgrade configuration files written
+//! for older versions of the `Schema` to the current layout.
+//!
+//! Migrations operate on the untyped YAML document so that configurations
+//! which no longer deserialize into `Schema` can still be upgraded. Each
+//! migration upgrades the document by exactly one version and is applied in
+//! sequence until the document reaches `CURRENT_VERSION`.
+use crate::err::GutenError;
+
+use serde_yaml::{Mapping, Value};
+
+/// Version of the configuration layout produced by this release
+pub const CURRENT_VERSION: u64 = 1;
+
+/// Key under which the version of the configuration layout is stored
+const VERSION_KEY: &str = "Version";
+
+/// Migration upgrading a configuration from the version it is indexed by to
+/// the next one
+type Migration = fn(&mut Mapping) -> Result<(), GutenError>;
+
+/// Migrations indexed by the version they upgrade from
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize] = [v0_to_v1];
+
+/// Returns the version of the configuration layout, configurations written
+/// before versioning was introduced are considered to be version `0`
+pub fn version(config: &Value) -> Result<u64, GutenError> {
+    match config.get(VERSION_KEY) {
+        None => Ok(0),
+        Some(version) => version.as_u64().ok_or_else(|| {
+            GutenError::InvalidConfig(format!(
+                "Configuration version must be a positive integer, got {version:?}"
+            ))
+        }),
+    }
+}
+
+/// Upgrades the configuration to `CURRENT_VERSION`, returns whether any
+/// migration was applied
+pub fn migrate(config: &mut Value) -> Result<bool, GutenError> {
+    let version = version(config)?;
+
+    if version > CURRENT_VERSION {
+        return Err(GutenError::InvalidConfig(format!(
+            "Configuration version {version} is newer than the latest supported version {CURRENT_VERSION}"
+        )));
+    }
+
+    let mapping = config.as_mapping_mut().ok_or_else(|| {
+        GutenError::InvalidConfig(
+            "Configuration must be a YAML mapping".to_string(),
+        )
+    })?;
+
+    if version < CURRENT_VERSION {
+        tracing::debug!(
+            "Migrating configuration from version {version} to {CURRENT_VERSION}"
+        );
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(mapping)?;
+    }
+
+    Ok(version < CURRENT_VERSION)
+}
+
+/// Version `1` introduced the `Version` field without changing the layout of
+/// the configuration
+fn v0_to_v1(config: &mut Mapping) -> Result<(), GutenError> {
+    config.insert(Value::from(VERSION_KEY), Value::from(1));
+    Ok(())
+}
@@ -1,3 +1,16 @@
-pub use crate::err::GutenError;
+pub use crate::annotate;
+pub use crate::builder::SchemaBuilder;
+#[cfg(feature = "cli")]
+pub use crate::check;
+pub use crate::constraints::Constraints;
+pub use crate::diff;
+pub use crate::err::{ErrorKind, GutenError};
+pub use crate::format::Format;
+pub use crate::import;
+pub use crate::migrations;
+pub use crate::report;
+pub use crate::requirements::Requirements;
+pub use crate::sanitize::{Modification, Sanitization};
 pub use crate::schema::*;
+pub use crate::template;
 pub use crate::types::*;
@@ -0,0 +1,235 @@
+//! Module containing the complexity report of the generated Move module.
+//!
+//! For every optional feature enabled in the `Schema`, the module is
+//! regenerated without the feature to measure how much generated surface the
+//! feature is responsible for, helping creators trim unnecessary code before
+//! an audit.
+use crate::err::GutenError;
+use crate::schema::Schema;
+use crate::types::{
+    CollectionMutability, Events, Listing, NftTags, Transferability,
+};
+
+use serde::Serialize;
+
+use std::fmt;
+
+/// Size metrics of generated Move source
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Metrics {
+    /// Number of function declarations
+    pub functions: usize,
+    /// Number of struct declarations
+    pub structs: usize,
+    /// Size of the generated source in bytes
+    pub bytes: usize,
+}
+
+impl Metrics {
+    /// Measures generated Move source
+    pub fn measure(source: &str) -> Metrics {
+        let declarations = source
+            .lines()
+            .map(str::trim_start)
+            .map(|line| line.trim_start_matches("public "))
+            .map(|line| line.trim_start_matches("entry "));
+
+        let (mut functions, mut structs) = (0, 0);
+        for line in declarations {
+            if line.starts_with("fun ") {
+                functions += 1;
+            } else if line.starts_with("struct ") {
+                structs += 1;
+            }
+        }
+
+        Metrics {
+            functions,
+            structs,
+            bytes: source.len(),
+        }
+    }
+}
+
+/// Complexity contributed by an optional feature
+#[derive(Debug, Clone, Serialize)]
+pub struct Feature {
+    pub name: &'static str,
+    /// Difference between the module with and without the feature
+    pub functions: isize,
+    pub structs: isize,
+    pub bytes: isize,
+}
+
+/// Complexity report of the module generated from a schema
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub module: Metrics,
+    /// Enabled optional features
+    pub features: Vec<Feature>,
+}
+
+/// Optional feature, its detection, and its removal from a schema
+struct Toggle {
+    name: &'static str,
+    is_enabled: fn(&Schema) -> bool,
+    disable: fn(&mut Schema),
+}
+
+const TOGGLES: [Toggle; 12] = [
+    Toggle {
+        name: "Marketplace",
+        is_enabled: |schema| schema.marketplace.is_some(),
+        disable: |schema| schema.marketplace = None,
+    },
+    Toggle {
+        name: "Listings",
+        is_enabled: |schema| schema.listings.iter().flatten().next().is_some(),
+        disable: |schema| schema.listings = None,
+    },
+    Toggle {
+        name: "Listing schedules",
+        is_enabled: |schema| {
+            schema.listings.iter().flatten().any(Listing::has_schedule)
+        },
+        disable: |schema| {
+            schema
+                .listings
+                .iter_mut()
+                .flatten()
+                .for_each(Listing::clear_schedule)
+        },
+    },
+    Toggle {
+        name: "Auction fallbacks",
+        is_enabled: |schema| {
+            schema.listings.iter().flatten().any(Listing::has_fallbacks)
+        },
+        disable: |schema| {
+            schema
+                .listings
+                .iter_mut()
+                .flatten()
+                .for_each(Listing::clear_fallbacks)
+        },
+    },
+    Toggle {
+        name: "Open edition",
+        is_enabled: |schema| schema.open_edition.is_some(),
+        disable: |schema| schema.open_edition = None,
+    },
+    Toggle {
+        name: "Collection images",
+        is_enabled: |schema| {
+            schema.collection.cover_url.is_some()
+                || schema.collection.banner_url.is_some()
+        },
+        disable: |schema| {
+            schema.collection.cover_url = None;
+            schema.collection.banner_url = None;
+        },
+    },
+    Toggle {
+        name: "Collection updates",
+        is_enabled: |schema| schema.collection.mutable.is_enabled(),
+        disable: |schema| {
+            schema.collection.mutable = CollectionMutability::default()
+        },
+    },
+    Toggle {
+        name: "NFT tags",
+        is_enabled: |schema| schema.nft_tags != NftTags::None,
+        disable: |schema| schema.nft_tags = NftTags::None,
+    },
+    Toggle {
+        name: "Fields",
+        is_enabled: |schema| !schema.fields.is_empty(),
+        disable: |schema| {
+            schema.fields.clear();
+            // Events may only carry custom fields
+            schema.events = Events::default();
+        },
+    },
+    Toggle {
+        name: "Burnable",
+        is_enabled: |schema| schema.burnable.is_some(),
+        disable: |schema| {
+            schema.burnable = None;
+            schema.events.burn = None;
+        },
+    },
+    Toggle {
+        name: "Events",
+        is_enabled: |schema| {
+            schema.events.mint.is_some() || schema.events.burn.is_some()
+        },
+        disable: |schema| {
+            schema.events.mint = None;
+            schema.events.burn = None;
+        },
+    },
+    Toggle {
+        name: "Transferability",
+        is_enabled: |schema| {
+            schema.transferability != Transferability::Transferable
+        },
+        disable: |schema| {
+            schema.transferability = Transferability::Transferable
+        },
+    },
+];
+
+/// Measures the module generated from the schema and the contribution of
+/// each enabled optional feature
+pub fn report(schema: &Schema) -> Result<Report, GutenError> {
+    let module = measure(schema)?;
+
+    let mut features = Vec::new();
+    for toggle in TOGGLES.iter().filter(|t| (t.is_enabled)(schema)) {
+        let mut without = schema.clone();
+        (toggle.disable)(&mut without);
+        let without = measure(&without)?;
+
+        features.push(Feature {
+            name: toggle.name,
+            functions: module.functions as isize - without.functions as isize,
+            structs: module.structs as isize - without.structs as isize,
+            bytes: module.bytes as isize - without.bytes as isize,
+        });
+    }
+
+    Ok(Report { module, features })
+}
+
+fn measure(schema: &Schema) -> Result<Metrics, GutenError> {
+    let mut output = Vec::new();
+    schema.write_move(&mut output)?;
+    Ok(Metrics::measure(&String::from_utf8_lossy(&output)))
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<20} {:>10} {:>10} {:>10}",
+            "Feature", "Functions", "Structs", "Bytes"
+        )?;
+
+        for feature in &self.features {
+            writeln!(
+                f,
+                "{:<20} {:>+10} {:>+10} {:>+10}",
+                feature.name, feature.functions, feature.structs, feature.bytes
+            )?;
+        }
+
+        write!(
+            f,
+            "{:<20} {:>10} {:>10} {:>10}",
+            "Total",
+            self.module.functions,
+            self.module.structs,
+            self.module.bytes
+        )
+    }
+}
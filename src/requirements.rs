@@ -0,0 +1,67 @@
+//! Module containing the version requirements which configurations and
+//! template packs can declare on the components generating their Move code.
+//!
+//! Requirements are checked before generation such that an out of range
+//! component is reported by name instead of surfacing as a package which
+//! does not compile.
+use crate::err::GutenError;
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+/// Version of the Gutenberg tool
+pub const GUTENBERG_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the `NftProtocol` dependency of the generated package, see
+/// `examples/packages/Move.toml`
+pub const PROTOCOL_VERSION: &str = "0.16.0";
+
+/// Version ranges of the components which are compatible with a
+/// configuration or template pack
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Requirements {
+    /// Compatible versions of the Gutenberg tool
+    pub gutenberg: Option<VersionReq>,
+    /// Compatible versions of the `NftProtocol` package
+    pub nft_protocol: Option<VersionReq>,
+}
+
+impl Requirements {
+    /// Parses requirements from YAML
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Requirements, GutenError> {
+        Ok(serde_yaml::from_reader(reader)?)
+    }
+
+    /// Checks that the versions of all components satisfy the requirements
+    /// declared by the named source, listing every component out of range
+    pub fn check(&self, source: &str) -> Result<(), GutenError> {
+        let components = [
+            ("gutenberg", &self.gutenberg, GUTENBERG_VERSION),
+            ("nft_protocol", &self.nft_protocol, PROTOCOL_VERSION),
+        ];
+
+        let violations = components
+            .into_iter()
+            .filter_map(|(component, requirement, version)| {
+                let requirement = requirement.as_ref()?;
+                let version = Version::parse(version)
+                    .expect("Component versions must be valid");
+
+                (!requirement.matches(&version)).then(|| {
+                    format!(
+                        "{source} requires {component} {requirement} but {component} is {version}"
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(GutenError::Incompatible(violations.join("; ")))
+        }
+    }
+}
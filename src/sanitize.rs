@@ -0,0 +1,377 @@
+//! Module containing the sanitization applied to strings which are written
+//! into the generated Move code and end up stored on-chain, as well as the
+//! derivation of valid Move identifiers from the collection name.
+//!
+//! Sanitization strips control characters, normalizes unicode to NFC, and
+//! truncates values exceeding the maximum byte length of their field. URLs
+//! are additionally normalized, such as by punycode encoding international
+//! domain names. Under the `Strict` policy any value requiring sanitization
+//! is instead rejected.
+use crate::err::GutenError;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use url::Url;
+
+use std::fmt;
+
+/// Maximum byte length of the collection name
+pub const MAX_NAME_LENGTH: usize = 64;
+/// Maximum byte length of the collection description
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+/// Maximum byte length of the collection symbol
+pub const MAX_SYMBOL_LENGTH: usize = 16;
+/// Maximum byte length of the collection URL
+pub const MAX_URL_LENGTH: usize = 2048;
+/// Maximum byte length of a custom tag
+pub const MAX_TAG_LENGTH: usize = 64;
+
+/// Move keywords which cannot be used as identifiers
+const KEYWORDS: [&str; 29] = [
+    "abort", "acquires", "address", "as", "break", "const", "continue", "copy",
+    "else", "entry", "false", "friend", "fun", "has", "if", "let", "loop",
+    "module", "move", "mut", "native", "phantom", "public", "return", "script",
+    "spec", "struct", "true", "use",
+];
+
+/// Module aliases imported by the generated module which the module name
+/// would shadow
+const IMPORTED_MODULES: [&str; 13] = [
+    "string",
+    "url",
+    "balance",
+    "transfer",
+    "tx_context",
+    "nft",
+    "tags",
+    "royalty",
+    "display",
+    "creators",
+    "inventory",
+    "royalties",
+    "collection",
+];
+
+/// Primitive Move types which may be used as type arguments
+const PRIMITIVE_TYPES: [&str; 7] =
+    ["bool", "u8", "u16", "u32", "u64", "u128", "address"];
+
+/// Schemes of the URLs which may be stored on-chain
+const URL_SCHEMES: [&str; 3] = ["http", "https", "ipfs"];
+
+/// Prefix of identifiers derived from names starting with a digit
+const IDENTIFIER_PREFIX: &str = "nft_";
+
+/// Determines how strings requiring sanitization are handled
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize,
+)]
+pub enum Sanitization {
+    /// Reject any string which would be modified by sanitization
+    Strict,
+    /// Sanitize strings and report the modified values
+    #[default]
+    Lenient,
+}
+
+/// Records a value that was modified by sanitization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Modification {
+    pub field: &'static str,
+    pub original: String,
+    pub sanitized: String,
+}
+
+impl fmt::Display for Modification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} was sanitized from {:?} to {:?}",
+            self.field, self.original, self.sanitized
+        )
+    }
+}
+
+impl Sanitization {
+    /// Sanitizes the value of the field in place, returns the modification
+    /// if the value changed, or an error if the policy is `Strict`
+    pub fn apply(
+        self,
+        field: &'static str,
+        value: &mut Box<str>,
+        max_length: usize,
+    ) -> Result<Option<Modification>, GutenError> {
+        let sanitized = sanitize(value, max_length);
+        self.update(field, value, sanitized, &format!(
+            "contains control characters, non-normalized unicode, or exceeds {max_length} bytes"
+        ))
+    }
+
+    /// Sanitizes the URL of the field in place like `Sanitization::apply`,
+    /// additionally normalizing it, see `normalize_url`
+    pub fn apply_url(
+        self,
+        field: &'static str,
+        value: &mut Box<str>,
+        ipfs_gateway: Option<&str>,
+    ) -> Result<Option<Modification>, GutenError> {
+        let sanitized =
+            normalize_url(&sanitize(value, MAX_URL_LENGTH), ipfs_gateway)
+                .map_err(|err| {
+                    GutenError::InvalidConfig(format!("{field}: {err}"))
+                })?;
+        self.update(field, value, sanitized, &format!(
+            "contains control characters, exceeds {MAX_URL_LENGTH} bytes, or is not a normalized URL"
+        ))
+    }
+
+    /// Replaces the value of the field by its sanitized value, returns the
+    /// modification if the value changed, or an error stating the `reason`
+    /// the value requires sanitization if the policy is `Strict`
+    fn update(
+        self,
+        field: &'static str,
+        value: &mut Box<str>,
+        sanitized: String,
+        reason: &str,
+    ) -> Result<Option<Modification>, GutenError> {
+        if *sanitized == **value {
+            return Ok(None);
+        }
+
+        let modification = Modification {
+            field,
+            original: value.to_string(),
+            sanitized,
+        };
+
+        match self {
+            Sanitization::Strict => {
+                Err(GutenError::InvalidConfig(format!("{field} {reason}")))
+            }
+            Sanitization::Lenient => {
+                *value = modification.sanitized.clone().into_boxed_str();
+                Ok(Some(modification))
+            }
+        }
+    }
+}
+
+/// Strips control characters, normalizes to NFC, and truncates the value to
+/// `max_length` bytes on a character boundary
+pub fn sanitize(value: &str, max_length: usize) -> String {
+    let mut sanitized = value
+        .chars()
+        .filter(|c| !c.is_control())
+        .nfc()
+        .collect::<String>();
+
+    if sanitized.len() > max_length {
+        let boundary = (0..=max_length)
+            .rev()
+            .find(|&i| sanitized.is_char_boundary(i))
+            .unwrap_or(0);
+        sanitized.truncate(boundary);
+    }
+
+    sanitized
+}
+
+/// Escapes a value written into a Move byte string, used as the escape
+/// function of the templates.
+///
+/// Backslashes and double quotes are escaped, and every byte outside of
+/// printable ASCII, such as the UTF-8 encoding of non-ASCII characters, is
+/// written as a `\xHH` escape.
+pub fn escape_byte_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'\\' | b'"' => {
+                escaped.push('\\');
+                escaped.push(byte as char);
+            }
+            b' '..=b'~' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    escaped
+}
+
+/// Parses the URL, which must not contain whitespace and must use the
+/// `http`, `https`, or `ipfs` scheme, and returns its normalized form.
+///
+/// International domain names are punycode encoded, and `ipfs://<cid>/<path>`
+/// URLs are rewritten to `<gateway>/ipfs/<cid>/<path>` if a gateway is
+/// provided.
+pub fn normalize_url(
+    value: &str,
+    ipfs_gateway: Option<&str>,
+) -> Result<String, String> {
+    if value.contains(char::is_whitespace) {
+        return Err(format!("URL {value:?} must not contain whitespace"));
+    }
+
+    let url = Url::parse(value)
+        .map_err(|err| format!("URL {value:?} is invalid, {err}"))?;
+
+    if !URL_SCHEMES.contains(&url.scheme()) {
+        return Err(format!(
+            "URL {value:?} must use one of the schemes {}",
+            URL_SCHEMES.join(", ")
+        ));
+    }
+
+    if url.scheme() != "ipfs" {
+        return Ok(url.to_string());
+    }
+
+    let cid =
+        url.host_str()
+            .filter(|cid| !cid.is_empty())
+            .ok_or_else(|| {
+                format!("URL {value:?} must be of the form ipfs://<cid>")
+            })?;
+
+    match ipfs_gateway {
+        Some(gateway) => {
+            let mut rewritten = format!(
+                "{}/ipfs/{cid}{}",
+                gateway.trim_end_matches('/'),
+                url.path()
+            );
+            if let Some(query) = url.query() {
+                rewritten.push('?');
+                rewritten.push_str(query);
+            }
+            Ok(rewritten)
+        }
+        None => Ok(url.to_string()),
+    }
+}
+
+/// Deterministically derives a lower snake case Move identifier from a name,
+/// such that `Cool Apes #1!` becomes `cool_apes_1`.
+///
+/// Runs of characters which are not ASCII alphanumeric are replaced by a
+/// single underscore, and names starting with a digit are prefixed. The
+/// result may still be empty, a keyword, or collide with an imported module,
+/// which `check_module_name` detects.
+pub fn identifier(name: &str) -> String {
+    let identifier = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{IDENTIFIER_PREFIX}{identifier}")
+    } else {
+        identifier
+    }
+}
+
+/// Checks whether the identifier is a lower snake case Move identifier
+pub fn is_identifier(identifier: &str) -> bool {
+    identifier.starts_with(|c: char| c.is_ascii_lowercase())
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Checks whether the string is a Move identifier of any case
+fn is_move_identifier(identifier: &str) -> bool {
+    identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && identifier != "_"
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Checks whether the string is a fully qualified Move struct type of the
+/// form `address::module::Type`, optionally with type arguments such as
+/// `0x2::coin::Coin<0x2::sui::SUI>`
+pub fn is_type_path(path: &str) -> bool {
+    let (base, arguments) = match path.split_once('<') {
+        Some((base, rest)) => match rest.strip_suffix('>') {
+            Some(arguments) => (base, Some(arguments)),
+            None => return false,
+        },
+        None => (path, None),
+    };
+
+    let segments: Vec<&str> = base.split("::").collect();
+    let [address, module, name] = segments[..] else {
+        return false;
+    };
+
+    let is_address = match address.strip_prefix("0x") {
+        Some(hex) => {
+            !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => is_move_identifier(address),
+    };
+
+    is_address
+        && is_move_identifier(module)
+        && is_move_identifier(name)
+        && arguments.is_none_or(|arguments| {
+            type_arguments(arguments).is_some_and(|arguments| {
+                arguments.into_iter().all(|argument| {
+                    PRIMITIVE_TYPES.contains(&argument)
+                        || is_type_path(argument)
+                })
+            })
+        })
+}
+
+/// Splits type arguments on their top level commas, returns `None` if the
+/// angle brackets are unbalanced
+fn type_arguments(arguments: &str) -> Option<Vec<&str>> {
+    let mut split = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                split.push(arguments[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return None;
+    }
+
+    split.push(arguments[start..].trim());
+    Some(split)
+}
+
+/// Checks whether the identifier is a Move keyword
+pub fn is_keyword(identifier: &str) -> bool {
+    KEYWORDS.contains(&identifier)
+}
+
+/// Checks that the module name is a valid Move identifier which does not
+/// collide with keywords or the modules imported by the generated module
+pub fn check_module_name(identifier: &str) -> Result<(), String> {
+    if !is_identifier(identifier) {
+        Err(format!(
+            "Module name {identifier:?} must start with a lowercase letter and only contain lowercase letters, digits, and underscores"
+        ))
+    } else if is_keyword(identifier) {
+        Err(format!("Module name {identifier:?} is a Move keyword"))
+    } else if IMPORTED_MODULES.contains(&identifier) {
+        Err(format!(
+            "Module name {identifier:?} collides with a module imported by the generated code"
+        ))
+    } else {
+        Ok(())
+    }
+}
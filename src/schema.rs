@@ -3,29 +3,61 @@
 //! the associated Move module and dump into a default or custom folder defined
 //! by the caller.
 use crate::err::GutenError;
-use crate::types::{Listing, Marketplace, NftType, Tag};
+use crate::format::Format;
+use crate::migrations;
+use crate::requirements::Requirements;
+use crate::sanitize::{self, Modification, Sanitization};
+use crate::template;
+use crate::types::{
+    Burnable, CollectionMutability, Event, Events, Field, Listing, Marketplace,
+    NftTags, NftType, OpenEdition, Tag, Transferability,
+};
 
-use serde::Deserialize;
-use strfmt::strfmt;
-
-use std::collections::HashMap;
-use std::fmt::Write;
-use std::fs;
+use serde::{Deserialize, Serialize};
+use tera::{Context, Tera};
 
 /// Struct that acts as an intermediate data structure representing the yaml
 /// configuration of the NFT collection.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Schema {
+    /// Version of the configuration layout, see `migrations`
+    pub version: u64,
+    /// Versions of the components generating the package which the
+    /// configuration is compatible with
+    #[serde(default)]
+    pub requires: Requirements,
     pub collection: Collection,
     pub nft_type: NftType,
+    /// Whether NFTs can be transferred after they are minted
+    #[serde(default)]
+    pub transferability: Transferability,
+    /// Whether NFTs carry tags
+    #[serde(default)]
+    pub nft_tags: NftTags,
+    /// Allows NFT owners to burn their NFTs
+    pub burnable: Option<Burnable>,
+    /// Custom typed fields stored on every NFT
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    /// Events emitted by the generated module
+    #[serde(default)]
+    pub events: Events,
     /// Creates a new marketplace with the collection
     pub marketplace: Option<Marketplace>,
     pub listings: Option<Vec<Listing>>,
+    /// Sells NFTs sharing the collection metadata within a mint window
+    pub open_edition: Option<OpenEdition>,
+    /// Policy for strings written on-chain requiring sanitization
+    #[serde(default)]
+    pub sanitization: Sanitization,
+    /// Gateway to which `ipfs://` URLs are rewritten, such as
+    /// `https://ipfs.io`
+    pub ipfs_gateway: Option<String>,
 }
 
 /// Contains the metadata fields of the collection
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Collection {
     /// The name of the collection
     pub name: Box<str>,
@@ -35,19 +67,397 @@ pub struct Collection {
     pub symbol: Box<str>,
     /// A set of strings that categorize the domain in which the NFT operates
     pub tags: Vec<Tag>,
-    /// The royalty fees creators accumulate on the sale of NFTs
+    /// The royalty fees creators accumulate on the sale of NFTs, either in
+    /// basis points such as `250` or `250bps`, or as a percentage such as
+    /// `2.5%`
     pub royalty_fee_bps: Box<str>,
     /// Field for extra data
     pub url: Box<str>,
+    /// URL of the cover image of the collection
+    pub cover_url: Option<Box<str>>,
+    /// URL of the banner image of the collection
+    pub banner_url: Option<Box<str>>,
+    /// Name of the generated Move module, derived from the collection name
+    /// if not set
+    pub module_name: Option<Box<str>>,
+    /// Collection fields which can be updated after deployment
+    #[serde(default)]
+    pub mutable: CollectionMutability,
+}
+
+impl Collection {
+    /// Parses the royalty into basis points, rejecting royalties exceeding
+    /// 100% of the sale price
+    pub fn royalty_bps(&self) -> Result<u64, String> {
+        let royalty = self.royalty_fee_bps.trim();
+        let invalid = || {
+            format!(
+                "Royalty {royalty:?} must be given in basis points, such as 250 or 250bps, or as a percentage with at most two decimals, such as 2.5%"
+            )
+        };
+
+        let bps = if let Some(percentage) = royalty.strip_suffix('%') {
+            let percentage = percentage.trim_end();
+            let (integer, fraction) =
+                percentage.split_once('.').unwrap_or((percentage, ""));
+            if fraction.len() > 2
+                || !fraction.chars().all(|c| c.is_ascii_digit())
+            {
+                return Err(invalid());
+            }
+
+            let integer = integer.parse::<u64>().map_err(|_| invalid())?;
+            let fraction =
+                format!("{fraction:0<2}").parse::<u64>().unwrap_or(0);
+            integer
+                .checked_mul(100)
+                .and_then(|bps| bps.checked_add(fraction))
+                .ok_or_else(invalid)?
+        } else {
+            royalty
+                .strip_suffix("bps")
+                .unwrap_or(royalty)
+                .trim_end()
+                .parse::<u64>()
+                .map_err(|_| invalid())?
+        };
+
+        if bps > 10_000 {
+            return Err(format!(
+                "Royalty of {bps} bps exceeds 100% of the sale price"
+            ));
+        }
+
+        Ok(bps)
+    }
 }
 
 impl Schema {
-    pub fn module_name(&self) -> Box<str> {
+    /// Parses a YAML configuration, migrating it to the current layout if it
+    /// was written for an older version of the schema
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Schema, GutenError> {
+        Schema::from_reader_with(Format::Yaml, reader)
+    }
+
+    /// Parses a configuration in the given format, see `Schema::from_reader`
+    pub fn from_reader_with<R: std::io::Read>(
+        format: Format,
+        reader: R,
+    ) -> Result<Schema, GutenError> {
+        tracing::debug!("Parsing {format:?} configuration");
+        let mut config = format.read(reader)?;
+        migrations::migrate(&mut config)?;
+        Ok(serde_yaml::from_value(config)?)
+    }
+
+    /// Checks the semantic constraints of the configuration which cannot be
+    /// expressed through deserialization alone
+    pub fn validate(&self) -> Result<(), GutenError> {
+        self.requires.check("Configuration")?;
+
         self.collection
-            .name
-            .to_lowercase()
-            .replace(' ', "_")
-            .into_boxed_str()
+            .royalty_bps()
+            .map_err(GutenError::InvalidConfig)?;
+
+        sanitize::check_module_name(&self.module_name())
+            .map_err(GutenError::InvalidConfig)?;
+
+        if let Some(gateway) = &self.ipfs_gateway {
+            if !gateway.starts_with("http://")
+                && !gateway.starts_with("https://")
+            {
+                return Err(GutenError::InvalidConfig(format!(
+                    "IPFS gateway {gateway:?} must be an http or https URL"
+                )));
+            }
+            sanitize::normalize_url(gateway, None)
+                .map_err(GutenError::InvalidConfig)?;
+        }
+
+        // An empty collection URL is left unset
+        let urls = [
+            Some(&self.collection.url).filter(|url| !url.is_empty()),
+            self.collection.cover_url.as_ref(),
+            self.collection.banner_url.as_ref(),
+        ];
+        for url in urls.into_iter().flatten() {
+            // URLs are validated as they are stored after sanitization
+            sanitize::normalize_url(
+                &sanitize::sanitize(url, sanitize::MAX_URL_LENGTH),
+                self.ipfs_gateway.as_deref(),
+            )
+            .map_err(GutenError::InvalidConfig)?;
+        }
+
+        for tag in &self.collection.tags {
+            tag.validate().map_err(GutenError::InvalidConfig)?;
+        }
+
+        if self.nft_tags == NftTags::Inherit && self.collection.tags.is_empty()
+        {
+            return Err(GutenError::InvalidConfig(
+                "NFTs cannot inherit the tags of a collection without tags"
+                    .to_string(),
+            ));
+        }
+
+        for (index, field) in self.fields.iter().enumerate() {
+            field.validate().map_err(GutenError::InvalidConfig)?;
+
+            if self.fields[..index].iter().any(|f| f.name == field.name) {
+                return Err(GutenError::InvalidConfig(format!(
+                    "Field name {:?} is defined more than once",
+                    field.name
+                )));
+            }
+        }
+
+        // Accessors of fields must not collide with the update functions of
+        // mutable fields
+        for field in self.fields.iter().filter(|field| field.mutable) {
+            let update = format!("update_{}", field.name);
+            let apply = format!("apply_{}_update", field.name);
+
+            if let Some(other) = self
+                .fields
+                .iter()
+                .find(|f| f.name == update || f.name == apply)
+            {
+                return Err(GutenError::InvalidConfig(format!(
+                    "Field name {:?} collides with a function generated for the mutable field {:?}",
+                    other.name, field.name
+                )));
+            }
+        }
+
+        if let Some(marketplace) = &self.marketplace {
+            marketplace.validate().map_err(GutenError::InvalidConfig)?;
+        }
+
+        for listing in self.listings.iter().flatten() {
+            listing.validate().map_err(GutenError::InvalidConfig)?;
+        }
+
+        if let Some(open_edition) = &self.open_edition {
+            open_edition.validate().map_err(GutenError::InvalidConfig)?;
+
+            sanitize::normalize_url(
+                &sanitize::sanitize(
+                    open_edition.url(),
+                    sanitize::MAX_URL_LENGTH,
+                ),
+                self.ipfs_gateway.as_deref(),
+            )
+            .map_err(GutenError::InvalidConfig)?;
+
+            // Open edition NFTs are minted without custom values
+            if !self.fields.is_empty() {
+                return Err(GutenError::InvalidConfig(
+                    "Open editions cannot be minted with custom Fields"
+                        .to_string(),
+                ));
+            }
+            if self.nft_tags == NftTags::PerToken {
+                return Err(GutenError::InvalidConfig(
+                    "Open editions cannot be minted with PerToken NFT tags"
+                        .to_string(),
+                ));
+            }
+            if self.transferability != Transferability::Transferable {
+                return Err(GutenError::InvalidConfig(format!(
+                    "{:?} NFTs cannot be sold through an OpenEdition",
+                    self.transferability
+                )));
+            }
+        }
+
+        if self.events.burn.is_some() && self.burnable.is_none() {
+            return Err(GutenError::InvalidConfig(
+                "Burn events require the collection to be Burnable".to_string(),
+            ));
+        }
+
+        self.events_context()?;
+
+        // Shared objects cannot be deleted
+        if self.transferability == Transferability::CreatorRevocable
+            && self.burnable.is_some()
+        {
+            return Err(GutenError::InvalidConfig(
+                "CreatorRevocable NFTs cannot be Burnable".to_string(),
+            ));
+        }
+
+        // The tag domain cannot be removed from the NFT before deleting it
+        if self.nft_tags != NftTags::None && self.burnable.is_some() {
+            return Err(GutenError::InvalidConfig(
+                "NFTs carrying tags cannot be Burnable".to_string(),
+            ));
+        }
+
+        // Soulbound NFTs are minted directly to their receiver
+        if self.transferability != Transferability::Transferable
+            && self.listings.iter().flatten().next().is_some()
+        {
+            return Err(GutenError::InvalidConfig(format!(
+                "{:?} NFTs cannot be sold through Listings",
+                self.transferability
+            )));
+        }
+
+        tracing::debug!(
+            "Validated configuration of collection {:?}",
+            self.collection.name
+        );
+        Ok(())
+    }
+
+    /// Sanitizes and then validates the schema, such that the checks apply
+    /// to the values from which the Move code is generated, returns the list
+    /// of modified values
+    pub fn prepare(&mut self) -> Result<Vec<Modification>, GutenError> {
+        let modifications = self.sanitize()?;
+        self.validate()?;
+        Ok(modifications)
+    }
+
+    /// Sanitizes the strings which are stored on-chain according to the
+    /// `Sanitization` policy, returns the list of modified values
+    pub fn sanitize(&mut self) -> Result<Vec<Modification>, GutenError> {
+        let policy = self.sanitization;
+        let gateway = self.ipfs_gateway.as_deref();
+        let collection = &mut self.collection;
+
+        let modifications = [
+            policy.apply(
+                "Collection name",
+                &mut collection.name,
+                sanitize::MAX_NAME_LENGTH,
+            )?,
+            policy.apply(
+                "Collection description",
+                &mut collection.description,
+                sanitize::MAX_DESCRIPTION_LENGTH,
+            )?,
+            policy.apply(
+                "Collection symbol",
+                &mut collection.symbol,
+                sanitize::MAX_SYMBOL_LENGTH,
+            )?,
+        ];
+
+        let mut modifications: Vec<_> =
+            modifications.into_iter().flatten().collect();
+
+        for tag in &mut collection.tags {
+            if let Tag::Custom(name) = tag {
+                let mut value = name.as_str().into();
+                if let Some(modification) = policy.apply(
+                    "Collection tag",
+                    &mut value,
+                    sanitize::MAX_TAG_LENGTH,
+                )? {
+                    *name = value.into();
+                    modifications.push(modification);
+                }
+            }
+        }
+
+        if !collection.url.is_empty() {
+            modifications.extend(policy.apply_url(
+                "Collection url",
+                &mut collection.url,
+                gateway,
+            )?);
+        }
+
+        for (field, url) in [
+            ("Collection cover_url", &mut collection.cover_url),
+            ("Collection banner_url", &mut collection.banner_url),
+        ] {
+            if let Some(url) = url {
+                modifications.extend(policy.apply_url(field, url, gateway)?);
+            }
+        }
+
+        if let Some(open_edition) = &mut self.open_edition {
+            modifications.extend(policy.apply_url(
+                "OpenEdition url",
+                open_edition.url_mut(),
+                gateway,
+            )?);
+        }
+
+        tracing::debug!("Sanitized {} values", modifications.len());
+        Ok(modifications)
+    }
+
+    /// Lists the configuration fields alongside their values as written in
+    /// the generated Move code, used to trace generated code back to the
+    /// configuration
+    pub fn field_values(&self) -> Vec<(String, String)> {
+        let collection = &self.collection;
+
+        let mut fields = vec![
+            ("Collection.name".to_string(), collection.name.to_string()),
+            (
+                "Collection.description".to_string(),
+                collection.description.to_string(),
+            ),
+            (
+                "Collection.symbol".to_string(),
+                collection.symbol.to_string(),
+            ),
+            ("Collection.url".to_string(), collection.url.to_string()),
+            (
+                "Collection.royalty_fee_bps".to_string(),
+                // Royalties are written in basis points
+                collection.royalty_bps().map_or_else(
+                    |_| collection.royalty_fee_bps.to_string(),
+                    |bps| bps.to_string(),
+                ),
+            ),
+        ];
+
+        for (field, url) in [
+            ("Collection.cover_url", &collection.cover_url),
+            ("Collection.banner_url", &collection.banner_url),
+        ] {
+            if let Some(url) = url {
+                fields.push((field.to_string(), url.to_string()));
+            }
+        }
+
+        if let Some(marketplace) = &self.marketplace {
+            fields.extend(marketplace.field_values("Marketplace"));
+        }
+
+        for (index, listing) in self.listings.iter().flatten().enumerate() {
+            fields.extend(listing.field_values(&format!("Listings[{index}]")));
+        }
+
+        if let Some(open_edition) = &self.open_edition {
+            fields.extend(open_edition.field_values("OpenEdition"));
+        }
+
+        fields
+    }
+
+    /// Name of the generated Move module, either as configured or derived
+    /// from the collection name
+    pub fn module_name(&self) -> Box<str> {
+        match &self.collection.module_name {
+            Some(module_name) => module_name.clone(),
+            None => sanitize::identifier(&self.collection.name).into(),
+        }
+    }
+
+    /// Name of the one time witness, which Sui requires to be the module
+    /// name in uppercase
+    pub fn witness(&self) -> Box<str> {
+        self.module_name().to_uppercase().into_boxed_str()
     }
 
     /// Higher level method responsible for generating Move code from the
@@ -56,100 +466,140 @@ impl Schema {
     /// the caller.
     pub fn write_move<W: std::io::Write>(
         &self,
-        mut output: W,
+        output: W,
     ) -> Result<(), GutenError> {
-        let file_path = "templates/template.move";
-        let fmt = fs::read_to_string(file_path)
-            .expect("Should have been able to read the file");
+        self.write_move_with(&template::builtin(), output)
+    }
 
-        let module_name = self.module_name();
+    /// Generates Move code from the struct `Schema` using the provided set
+    /// of templates, see `template::from_dir`
+    pub fn write_move_with<W: std::io::Write>(
+        &self,
+        templates: &Tera,
+        output: W,
+    ) -> Result<(), GutenError> {
+        tracing::debug!(
+            "Rendering {} of {}",
+            template::MODULE,
+            self.module_name()
+        );
+        templates.render_to(template::MODULE, &self.context(), output)?;
+        Ok(())
+    }
 
-        let witness = self
-            .collection
-            .name
-            .to_uppercase()
-            .replace(' ', "")
-            .into_boxed_str();
+    /// Generates the Move unit tests of the module generated by
+    /// `Schema::write_move`, meant to be written to the `tests` directory of
+    /// the Move package
+    pub fn write_move_tests<W: std::io::Write>(
+        &self,
+        output: W,
+    ) -> Result<(), GutenError> {
+        self.write_move_tests_with(&template::builtin(), output)
+    }
+
+    /// Generates the Move unit tests using the provided set of templates
+    pub fn write_move_tests_with<W: std::io::Write>(
+        &self,
+        templates: &Tera,
+        output: W,
+    ) -> Result<(), GutenError> {
+        tracing::debug!(
+            "Rendering {} of {}",
+            template::TESTS,
+            self.module_name()
+        );
+        templates.render_to(template::TESTS, &self.context(), output)?;
+        Ok(())
+    }
+
+    /// Template context of the configured events
+    fn events_context(&self) -> Result<serde_json::Value, GutenError> {
+        let context = |event: &Option<Event>| {
+            event
+                .as_ref()
+                .map(|event| event.context(&self.fields))
+                .transpose()
+                .map_err(GutenError::InvalidConfig)
+        };
 
-        let tags = self.write_tags();
+        Ok(serde_json::json!({
+            "mint": context(&self.events.mint)?,
+            "burn": context(&self.events.burn)?,
+            "field_update": context(&self.events.field_update)?,
+        }))
+    }
 
-        let init_marketplace = self
-            .marketplace
-            .as_ref()
-            .map(Marketplace::init)
-            .unwrap_or_else(String::new)
-            .into_boxed_str();
+    /// Template context from which the Move code is rendered
+    pub fn context(&self) -> Context {
+        let (custom_tags, tags): (Vec<_>, Vec<_>) = self
+            .collection
+            .tags
+            .iter()
+            .partition(|tag| matches!(tag, Tag::Custom(_)));
+        let tags = tags.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let custom_tags = custom_tags
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
 
-        let init_listings = self
+        let listings = self
             .listings
             .iter()
             .flatten()
-            .map(Listing::init)
+            .enumerate()
+            .map(|(index, listing)| listing.context(index + 1))
             .collect::<Vec<_>>();
-        let init_listings = init_listings.join("\n    ").into_boxed_str();
-
-        // Collate list of objects that need to be shared
-        // TODO: Use Marketplace::init and Listing::init functions to avoid explicit share
-        let share_marketplace = self
-            .marketplace
-            .as_ref()
-            .map(Marketplace::share)
-            .unwrap_or_default()
-            .into_boxed_str();
-
-        let mut vars = HashMap::new();
-
-        vars.insert("module_name", &module_name);
-        vars.insert("witness", &witness);
-        vars.insert("name", &self.collection.name);
-        vars.insert("description", &self.collection.description);
-        vars.insert("url", &self.collection.url);
-        vars.insert("symbol", &self.collection.symbol);
-        vars.insert("royalty_fee_bps", &self.collection.royalty_fee_bps);
-        vars.insert("tags", &tags);
 
-        // Marketplace and Listing objects
-        vars.insert("init_marketplace", &init_marketplace);
-        vars.insert("init_listings", &init_listings);
-        vars.insert("share_marketplace", &share_marketplace);
-
-        let vars: HashMap<String, String> = vars
-            .into_iter()
-            .map(|(k, v)| (k.to_string(), v.to_string()))
-            .collect();
-
-        output.write_all(
-            strfmt(&fmt, &vars)
-                // This is expected not to result in an error since we
-                // have explicitly handled all error cases
-                .unwrap_or_else(|err| {
-                    panic!(
-                        "This error is not expected and should not occur: {}",
-                        err
-                    )
-                })
-                .as_bytes(),
-        )?;
-
-        Ok(())
-    }
+        let has_schedules =
+            self.listings.iter().flatten().any(Listing::has_schedule);
+        let has_fallbacks =
+            self.listings.iter().flatten().any(Listing::has_fallbacks);
 
-    /// Generates Move code to push tags to a Move `vector` structure
-    pub fn write_tags(&self) -> Box<str> {
-        let mut out = String::from("let tags = tags::empty(ctx);\n");
+        let mut context = Context::new();
 
-        for tag in self.collection.tags.iter() {
-            out.write_fmt(format_args!(
-                "        tags::add_tag(&mut tags, tags::{}());\n",
-                tag.to_string()
-            ))
-            .unwrap();
-        }
+        context.insert("module_name", &self.module_name());
+        context.insert("witness", &self.witness());
+        context.insert("name", &self.collection.name);
+        context.insert("description", &self.collection.description);
+        context.insert("url", &self.collection.url);
+        context.insert("cover_url", &self.collection.cover_url);
+        context.insert("banner_url", &self.collection.banner_url);
+        context.insert("symbol", &self.collection.symbol);
+        context.insert(
+            "royalty_fee_bps",
+            // Royalty is checked to be valid by `Schema::validate`
+            &self.collection.royalty_bps().unwrap_or_default(),
+        );
+        context.insert("tags", &tags);
+        context.insert("custom_tags", &custom_tags);
+        context.insert("nft_tags", &self.nft_tags);
+        context.insert("collection_mutable", &self.collection.mutable);
+        context.insert("transferability", &self.transferability);
+        context.insert(
+            "events",
+            // Event fields are checked to exist by `Schema::validate`
+            &self.events_context().unwrap_or_default(),
+        );
+        context.insert("burnable", &self.burnable);
+        context.insert(
+            "fields",
+            &self.fields.iter().map(Field::context).collect::<Vec<_>>(),
+        );
+        context.insert(
+            "has_mutable_fields",
+            &self.fields.iter().any(|field| field.mutable),
+        );
 
-        out.push_str(
-            "        tags::add_collection_tag_domain(&mut collection, &mut mint_cap, tags);"
+        // Marketplace and Listing objects
+        context.insert("marketplace", &self.marketplace);
+        context.insert("listings", &listings);
+        context.insert("has_schedules", &has_schedules);
+        context.insert("has_fallbacks", &has_fallbacks);
+        context.insert(
+            "open_edition",
+            &self.open_edition.as_ref().map(OpenEdition::context),
         );
 
-        out.into_boxed_str()
+        context
     }
 }
@@ -0,0 +1,87 @@
+//! Module serving the generator over HTTP, such that launchpad backends can
+//! run Gutenberg as a service instead of invoking the CLI.
+//!
+//! Every endpoint accepts a configuration in YAML or JSON as the request
+//! body and responds with a JSON document in the layout printed by the
+//! `--json` flag of the CLI.
+use crate::err::{ErrorKind, GutenError};
+use crate::report;
+use crate::sanitize::Modification;
+use crate::schema::Schema;
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+
+use std::net::SocketAddr;
+
+/// Serves the endpoints on the address until the process is terminated
+pub async fn serve(addr: SocketAddr) -> Result<(), GutenError> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Serving on {}", listener.local_addr()?);
+    axum::serve(listener, router()).await?;
+    Ok(())
+}
+
+/// Routes of the `/validate`, `/generate`, and `/report` endpoints
+pub fn router() -> Router {
+    Router::new()
+        .route("/validate", post(validate))
+        .route("/generate", post(generate))
+        .route("/report", post(report))
+}
+
+/// Responds with the values modified by sanitization
+pub async fn validate(config: String) -> (StatusCode, Json<Value>) {
+    respond(&config, |_, modifications| {
+        Ok(json!({ "modifications": modifications }))
+    })
+}
+
+/// Responds with the generated Move module and tests
+pub async fn generate(config: String) -> (StatusCode, Json<Value>) {
+    respond(&config, |schema, modifications| {
+        let mut module = Vec::new();
+        schema.write_move(&mut module)?;
+        let mut tests = Vec::new();
+        schema.write_move_tests(&mut tests)?;
+
+        Ok(json!({
+            "module_name": schema.module_name(),
+            "module": String::from_utf8_lossy(&module),
+            "tests": String::from_utf8_lossy(&tests),
+            "modifications": modifications,
+        }))
+    })
+}
+
+/// Responds with the generated code size of the enabled features
+pub async fn report(config: String) -> (StatusCode, Json<Value>) {
+    respond(&config, |schema, _| Ok(json!(report::report(schema)?)))
+}
+
+/// Parses, sanitizes, and validates the configuration, and responds with the
+/// result of `f` or the error
+fn respond(
+    config: &str,
+    f: impl FnOnce(&Schema, Vec<Modification>) -> Result<Value, GutenError>,
+) -> (StatusCode, Json<Value>) {
+    let result =
+        Schema::from_reader(config.as_bytes()).and_then(|mut schema| {
+            let modifications = schema.prepare()?;
+            f(&schema, modifications)
+        });
+
+    match result {
+        Ok(response) => (StatusCode::OK, Json(response)),
+        Err(err) => {
+            let status = match err.kind() {
+                ErrorKind::Validation => StatusCode::UNPROCESSABLE_ENTITY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            let error = json!({ "error": err.to_string(), "kind": err.kind() });
+            (status, Json(error))
+        }
+    }
+}
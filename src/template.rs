@@ -0,0 +1,103 @@
+//! Module containing the templates from which the Move code is generated.
+//!
+//! The built-in templates are bundled with the crate, but each of them can be
+//! replaced by a template with the same name in a custom template directory,
+//! allowing third parties to customize the generated Move code without
+//! forking the crate. `extensions.move` is empty by default and serves as the
+//! hook for injecting custom Move functions into the collection module.
+#[cfg(feature = "cli")]
+use crate::err::GutenError;
+#[cfg(feature = "cli")]
+use crate::requirements::Requirements;
+use crate::sanitize;
+
+use tera::Tera;
+
+#[cfg(feature = "cli")]
+use std::{collections::HashMap, fs, path::Path};
+
+/// Name of the template rendering the collection module
+pub const MODULE: &str = "template.move";
+
+/// Name of the template appended to the end of the collection module
+pub const EXTENSIONS: &str = "extensions.move";
+
+/// Name of the template rendering the Move unit tests of the collection
+/// module
+pub const TESTS: &str = "tests.move";
+
+/// Name of the file declaring the version requirements of a template
+/// directory, see `Requirements`
+pub const REQUIREMENTS: &str = "requires.yaml";
+
+/// Names and contents of the built-in templates
+const BUILTIN: [(&str, &str); 3] = [
+    (MODULE, include_str!("../templates/template.move")),
+    (EXTENSIONS, include_str!("../templates/extensions.move")),
+    (TESTS, include_str!("../templates/tests.move")),
+];
+
+/// Loads the built-in templates
+pub fn builtin() -> Tera {
+    // Built-in templates are tested to be well-formed
+    load(BUILTIN).expect("Built-in templates must be valid")
+}
+
+/// Loads the templates, escaping every rendered value such that values
+/// cannot terminate the Move byte strings they are written into
+fn load<I, N, C>(templates: I) -> Result<Tera, tera::Error>
+where
+    I: IntoIterator<Item = (N, C)>,
+    N: AsRef<str>,
+    C: AsRef<str>,
+{
+    let mut tera = Tera::default();
+    tera.autoescape_on(vec![".move"]);
+    tera.set_escape_fn(sanitize::escape_byte_string);
+    tera.add_raw_templates(templates)?;
+    Ok(tera)
+}
+
+/// Loads the built-in templates, replacing any of them with the template of
+/// the same name in the directory.
+///
+/// Every `.move` file in the directory is loaded as a template named after
+/// its file name, such that overriding templates may include their own
+/// partials, whereas other files such as editor swap files are ignored. The
+/// `requires.yaml` file is instead checked as the version requirements of
+/// the templates.
+#[cfg(feature = "cli")]
+pub fn from_dir(dir: &Path) -> Result<Tera, GutenError> {
+    let mut templates = BUILTIN
+        .iter()
+        .map(|(name, template)| (name.to_string(), template.to_string()))
+        .collect::<HashMap<_, _>>();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name == REQUIREMENTS {
+            Requirements::from_reader(fs::File::open(entry.path())?)?
+                .check("Template directory")?;
+            continue;
+        }
+
+        if entry
+            .path()
+            .extension()
+            .is_none_or(|extension| extension != "move")
+        {
+            tracing::debug!("Skipping {name}, which is not a Move template");
+            continue;
+        }
+
+        tracing::debug!("Loading template {name} from {}", dir.display());
+        templates.insert(name, fs::read_to_string(entry.path())?);
+    }
+
+    Ok(load(templates)?)
+}
@@ -3,14 +3,37 @@
 //! String but should match to a value in a given Enum. Such Enums represent
 //! the type of NFTs available or the type of Markets available on our
 //! OriginByte protocol.
-use serde::Deserialize;
+use crate::sanitize;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use std::fmt;
 
 fn default_admin() -> String {
     "tx_context::sender(ctx)".to_string()
 }
 
+/// Lists the admin and receiver fields under the given prefix, skipping
+/// fields defaulting to the transaction sender as they cannot be traced back
+/// to the configuration
+fn address_fields(
+    prefix: &str,
+    admin: &str,
+    receiver: &str,
+) -> Vec<(String, String)> {
+    [("admin", admin), ("receiver", receiver)]
+        .into_iter()
+        .filter(|(_, address)| *address != default_admin())
+        .map(|(field, address)| {
+            (format!("{prefix}.{field}"), address.to_string())
+        })
+        .collect()
+}
+
 /// Enum representing the NFT types currently available in the protocol
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum NftType {
     // TODO: Need to add support for Soulbound
     Classic,
@@ -19,7 +42,9 @@ pub enum NftType {
     // CNft,
 }
 
-#[derive(Debug, Deserialize)]
+/// Tag categorizing the collection, either one of the standard tags of the
+/// protocol or a custom tag
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Tag {
     Art,
     ProfilePicture,
@@ -32,10 +57,81 @@ pub enum Tag {
     Video,
     Ticket,
     License,
+    /// Tag with a name which is not standard
+    Custom(String),
 }
 
 impl Tag {
-    pub fn to_string(&self) -> String {
+    /// Tags defined by the protocol
+    pub const STANDARD: [Tag; 11] = [
+        Tag::Art,
+        Tag::ProfilePicture,
+        Tag::Collectible,
+        Tag::GameAsset,
+        Tag::TokenisedAsset,
+        Tag::Ticker,
+        Tag::DomainName,
+        Tag::Music,
+        Tag::Video,
+        Tag::Ticket,
+        Tag::License,
+    ];
+
+    /// Name of the tag as written in the configuration
+    pub fn name(&self) -> &str {
+        match self {
+            Tag::Art => "Art",
+            Tag::ProfilePicture => "ProfilePicture",
+            Tag::Collectible => "Collectible",
+            Tag::GameAsset => "GameAsset",
+            Tag::TokenisedAsset => "TokenisedAsset",
+            Tag::Ticker => "Ticker",
+            Tag::DomainName => "DomainName",
+            Tag::Music => "Music",
+            Tag::Video => "Video",
+            Tag::Ticket => "Ticket",
+            Tag::License => "License",
+            Tag::Custom(name) => name,
+        }
+    }
+
+    /// Checks that custom tags are not empty
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Tag::Custom(name) if name.is_empty() => {
+                Err("Custom tags must not be empty".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    /// Names which are not standard tags are parsed as custom tags
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Tag::STANDARD
+            .into_iter()
+            .find(|tag| tag.name() == name)
+            .unwrap_or(Tag::Custom(name)))
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl fmt::Display for Tag {
+    /// Writes the name of the function of the `tags` module returning the
+    /// standard tag, or the name of custom tags
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let tag = match self {
             Tag::Art => "art",
             Tag::ProfilePicture => "profile_picture",
@@ -48,157 +144,467 @@ impl Tag {
             Tag::Video => "video",
             Tag::Ticket => "ticket",
             Tag::License => "license",
+            Tag::Custom(name) => name,
         };
 
-        tag.to_string()
-    }
-}
-
-impl NftType {
-    /// Writes Move code for an entry function meant to be called by
-    /// the Creators to mint NFTs. Depending on the NFTtype the function
-    /// parameters change, therefore pattern match the NFT type.
-    pub fn mint_func(&self, witness: &str) -> Box<str> {
-        let func = match self {
-            NftType::Classic => format!(
-                "public entry fun mint_nft(
-                    name: String,
-                    description: String,
-                    url: vector<u8>,
-                    attribute_keys: vector<String>,
-                    attribute_values: vector<String>,
-                    mint_cap: &mut MintCap<{witness}>,
-                    inventory: &mut Inventory,
-                    ctx: &mut TxContext,
-                ) {{
-                    let nft = nft::new<{witness}>(tx_context::sender(ctx), ctx);
-
-                    collection::increment_supply(mint_cap, 1);
-
-                    display::add_display_domain(
-                        &mut nft,
-                        name,
-                        description,
-                        ctx,
-                    );
-
-                    display::add_url_domain(
-                        &mut nft,
-                        url::new_unsafe_from_bytes(url),
-                        ctx,
-                    );
-
-                    display::add_attributes_domain_from_vec(
-                        &mut nft,
-                        attribute_keys,
-                        attribute_values,
-                        ctx,
-                    );
-
-                    inventory::deposit_nft(inventory, nft);
-                }}",
-                witness = witness,
-            ),
+        f.write_str(tag)
+    }
+}
+
+/// Determines whether NFTs of the collection can change hands
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize,
+)]
+pub enum Transferability {
+    /// NFTs are deposited in the listing inventory and can be freely traded
+    #[default]
+    Transferable,
+    /// NFTs are minted directly to their receiver wrapped in an object
+    /// without the `store` ability, which only the collection module could
+    /// transfer
+    Soulbound,
+    /// Soulbound NFTs are shared objects recording their owner, allowing the
+    /// creator to revoke them
+    CreatorRevocable,
+}
+
+/// Determines whether NFTs carry tags, which are stored in a domain of each
+/// NFT in addition to the tags of the collection
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize,
+)]
+pub enum NftTags {
+    /// NFTs carry no tags
+    #[default]
+    None,
+    /// NFTs carry the tags of the collection
+    Inherit,
+    /// NFTs carry the tags passed to `mint_nft`
+    PerToken,
+}
+
+/// Determines which collection fields can be updated after deployment by
+/// the holder of the `MintCap`, each generating an update entry function
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CollectionMutability {
+    #[serde(default)]
+    pub description: bool,
+    #[serde(default)]
+    pub url: bool,
+    #[serde(default)]
+    pub royalty_fee_bps: bool,
+}
+
+impl CollectionMutability {
+    pub fn is_enabled(&self) -> bool {
+        self.description || self.url || self.royalty_fee_bps
+    }
+}
+
+/// Determines which events the generated module emits
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Events {
+    /// Emit a `MintEvent` from `mint_nft`
+    pub mint: Option<Event>,
+    /// Emit a `BurnEvent` from `burn_nft`, requires `Burnable`
+    pub burn: Option<Event>,
+    /// Emit a `FieldUpdateEvent` from the update functions of mutable fields
+    pub field_update: Option<Event>,
+}
+
+impl Default for Events {
+    /// Only field updates are emitted unless configured otherwise
+    fn default() -> Self {
+        Events {
+            mint: None,
+            burn: None,
+            field_update: Some(Event::default()),
+        }
+    }
+}
+
+/// Configuration of an emitted event
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Event {
+    /// Names of custom NFT fields whose values are included in the event
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+impl Event {
+    /// Template context of the event, resolving the types of its fields
+    /// from the custom NFT fields
+    pub fn context(&self, fields: &[Field]) -> Result<Value, String> {
+        let event_fields = self
+            .fields
+            .iter()
+            .map(|name| {
+                fields
+                    .iter()
+                    .find(|field| &field.name == name)
+                    .map(Field::context)
+                    .ok_or_else(|| {
+                        format!(
+                            "Event field {name:?} is not a custom NFT field"
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(json!({ "fields": event_fields }))
+    }
+}
+
+/// Enables the generation of a `burn_nft` entry function
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Burnable {
+    /// Emit a `RedeemEvent` when an NFT is burned such that off-chain
+    /// services can honour the redemption
+    #[serde(default)]
+    pub redemption_event: bool,
+}
+
+/// Identifiers used by the generated `mint_nft` function and module which
+/// custom fields and their accessors must not shadow
+const RESERVED_FIELD_NAMES: [&str; 31] = [
+    "name",
+    "description",
+    "url",
+    "attribute_keys",
+    "attribute_values",
+    "inventory",
+    "receiver",
+    "ctx",
+    "nft",
+    "init",
+    "mint_nft",
+    "collect_royalty",
+    "init_for_testing",
+    "borrow_nft",
+    "revoke_nft",
+    "burn_nft",
+    "collection_description",
+    "collection_url",
+    "collection_royalty_fee_bps",
+    "update_collection_description",
+    "update_collection_url",
+    "update_collection_royalty_fee_bps",
+    "owner",
+    "is_revoked",
+    "nft_tags",
+    "tags",
+    "nft_id",
+    "field",
+    "fallback_auction",
+    "mint_open_edition",
+    "close_open_edition",
+];
+
+/// Custom typed field stored on every NFT of the collection
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Field {
+    /// Name of the field, must be a lower snake case Move identifier
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    /// Generate an `update_<name>` entry function allowing the holder of
+    /// the `MintCap` to issue updates of the field after mint, which the
+    /// owner of the NFT applies using `apply_<name>_update`
+    #[serde(default)]
+    pub mutable: bool,
+}
+
+/// Move types supported by custom NFT fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FieldType {
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "u8")]
+    U8,
+    #[serde(rename = "u16")]
+    U16,
+    #[serde(rename = "u32")]
+    U32,
+    #[serde(rename = "u64")]
+    U64,
+    #[serde(rename = "u128")]
+    U128,
+    #[serde(rename = "address")]
+    Address,
+    String,
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let move_type = match self {
+            FieldType::Bool => "bool",
+            FieldType::U8 => "u8",
+            FieldType::U16 => "u16",
+            FieldType::U32 => "u32",
+            FieldType::U64 => "u64",
+            FieldType::U128 => "u128",
+            FieldType::Address => "address",
+            FieldType::String => "String",
         };
-        func.into_boxed_str()
+
+        f.write_str(move_type)
+    }
+}
+
+impl Field {
+    /// Checks that the field name is a valid Move identifier which does not
+    /// collide with the identifiers of the generated code
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.as_str();
+
+        if !sanitize::is_identifier(name) {
+            Err(format!(
+                "Field name {name:?} must start with a lowercase letter and only contain lowercase letters, digits, and underscores"
+            ))
+        } else if sanitize::is_keyword(name) {
+            Err(format!("Field name {name:?} is a Move keyword"))
+        } else if RESERVED_FIELD_NAMES.contains(&name)
+            || name.starts_with("sale_on_listing_")
+        {
+            Err(format!(
+                "Field name {name:?} collides with an identifier of the generated code"
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Template context of the field
+    pub fn context(&self) -> Value {
+        json!({
+            "name": self.name,
+            "type": self.field_type.to_string(),
+            "mutable": self.mutable,
+        })
     }
 }
 
 /// Contains the market configurations of the marketplace
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Marketplace {
     #[serde(default = "default_admin")]
     admin: String,
+    /// Receiver of the marketplace commission
     #[serde(default = "default_admin")]
     receiver: String,
+    /// Commission charged by the marketplace on sales in basis points
+    #[serde(default)]
+    fee_bps: u64,
 }
 
 impl Marketplace {
-    pub fn init(&self) -> String {
-        format!(
-            "
-        let marketplace = nft_protocol::marketplace::new(
-            {admin},
-            {receiver},
-            nft_protocol::flat_fee::new(0, ctx),
-            ctx,
-        );
-",
-            admin = self.admin,
-            receiver = self.receiver,
-        )
-    }
-
-    pub fn share(&self) -> String {
-        "
-        transfer::share_object(marketplace);
-"
-        .to_string()
-    }
-}
-
-#[derive(Debug, Deserialize)]
+    /// Checks that the commission does not exceed 100% of the sale price
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fee_bps > 10_000 {
+            return Err(format!(
+                "Marketplace fee of {} bps exceeds 100% of the sale price",
+                self.fee_bps
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Lists the fields of the marketplace under the given prefix, see
+    /// `Schema::field_values`
+    pub fn field_values(&self, prefix: &str) -> Vec<(String, String)> {
+        let mut fields = address_fields(prefix, &self.admin, &self.receiver);
+        if self.fee_bps != 0 {
+            fields
+                .push((format!("{prefix}.fee_bps"), self.fee_bps.to_string()));
+        }
+        fields
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Listing {
     #[serde(default = "default_admin")]
     admin: String,
     #[serde(default = "default_admin")]
     receiver: String,
+    /// ISO-8601 timestamp from which the listing's venues may be opened
+    /// through the generated `sale_on_listing_<n>` function, compared
+    /// against the start of the current epoch
+    start: Option<DateTime<FixedOffset>>,
+    /// ISO-8601 timestamp after which the listing's venues may no longer be
+    /// opened through the generated `sale_on_listing_<n>` function,
+    /// compared against the start of the current epoch
+    end: Option<DateTime<FixedOffset>>,
+    /// Whether the markets of the listing sell from a single inventory
+    /// instead of an inventory each
+    #[serde(default)]
+    shared_inventory: bool,
     markets: Vec<Market>,
 }
 
 impl Listing {
-    pub fn init(&self) -> String {
-        let mut string = String::new();
+    /// Checks that the sale window is representable in epoch milliseconds
+    /// and that it starts before it ends, and that the tokens accepted by
+    /// its markets are valid type paths
+    pub fn validate(&self) -> Result<(), String> {
+        for time in [&self.start, &self.end].into_iter().flatten() {
+            if time.timestamp_millis() < 0 {
+                return Err(format!(
+                    "Listing sale time {time} must not be before the Unix epoch"
+                ));
+            }
+        }
+
+        if let (Some(start), Some(end)) = (&self.start, &self.end) {
+            if start >= end {
+                return Err(format!(
+                    "Listing sale start {start} must be before its end {end}"
+                ));
+            }
+        }
+
+        for market in &self.markets {
+            if let Some(fallback) = market.fixed_price_after() {
+                if fallback.timestamp_millis() < 0 {
+                    return Err(format!(
+                        "Auction fallback time {fallback} must not be before the Unix epoch"
+                    ));
+                }
+                if self.start.is_some_and(|start| *fallback <= start) {
+                    return Err(format!(
+                        "Auction fallback time {fallback} must be after the listing sale start"
+                    ));
+                }
+                if self.end.is_some_and(|end| *fallback >= end) {
+                    return Err(format!(
+                        "Auction fallback time {fallback} must be before the listing sale end"
+                    ));
+                }
+            }
+
+            let (token, _, _) = market.terms();
+            if !sanitize::is_type_path(token) {
+                return Err(format!(
+                    "Market token {token:?} is not a fully qualified Move type such as sui::sui::SUI"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn start_ms(&self) -> Option<i64> {
+        self.start.as_ref().map(DateTime::timestamp_millis)
+    }
+
+    pub fn end_ms(&self) -> Option<i64> {
+        self.end.as_ref().map(DateTime::timestamp_millis)
+    }
+
+    /// Lists the fields of the listing under the given prefix, see
+    /// `Schema::field_values`
+    pub fn field_values(&self, prefix: &str) -> Vec<(String, String)> {
+        let mut fields = address_fields(prefix, &self.admin, &self.receiver);
 
-        string.push_str(&format!(
-            "
-        let listing = nft_protocol::listing::new(
-            {admin},
-            {receiver},
-            ctx,
-        );
-",
-            admin = self.admin,
-            receiver = self.receiver,
-        ));
+        for (index, market) in self.markets.iter().enumerate() {
+            let (token, price, _) = market.terms();
+            let prefix = format!("{prefix}.markets[{index}]");
 
-        for market in self.markets.iter() {
-            string.push_str(&market.init());
+            fields.push((format!("{prefix}.token"), token.clone()));
+            fields.push((format!("{prefix}.price"), price.to_string()));
         }
 
-        string.push_str(self.share());
+        fields
+    }
+
+    pub fn markets(&self) -> &[Market] {
+        &self.markets
+    }
+
+    pub fn has_schedule(&self) -> bool {
+        self.start.is_some() || self.end.is_some()
+    }
+
+    /// Removes the sale window of the listing
+    pub fn clear_schedule(&mut self) {
+        self.start = None;
+        self.end = None;
+    }
+
+    pub fn has_fallbacks(&self) -> bool {
+        self.markets
+            .iter()
+            .any(|market| market.fixed_price_after().is_some())
+    }
 
-        string
+    /// Removes the fixed price fallbacks of the listing's auctions
+    pub fn clear_fallbacks(&mut self) {
+        self.markets.iter_mut().for_each(Market::clear_fallback);
     }
 
-    fn share(&self) -> &'static str {
-        "
-        transfer::share_object(listing);
-"
+    /// Template context of the listing.
+    ///
+    /// `index` is used to disambiguate between the functions and constants
+    /// of different listings within the same module.
+    pub fn context(&self, index: usize) -> Value {
+        json!({
+            "index": index,
+            "admin": self.admin,
+            "receiver": self.receiver,
+            "start_ms": self.start_ms(),
+            "end_ms": self.end_ms(),
+            "shared_inventory": self.shared_inventory,
+            "markets": self
+                .markets
+                .iter()
+                .map(Market::context)
+                .collect::<Vec<_>>(),
+        })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Market {
     FixedPrice {
         /// Fully qualified fungible token in which price is denominated
+        #[serde(default = "default_token")]
         token: String,
         price: u64,
         is_whitelisted: bool,
     },
     DutchAuction {
         /// Fully qualified fungible token in which price is denominated
+        #[serde(default = "default_token")]
         token: String,
         reserve_price: u64,
         is_whitelisted: bool,
+        /// ISO-8601 timestamp after which the auction may be replaced by a
+        /// fixed price venue selling at the reserve price, compared against
+        /// the start of the current epoch
+        fixed_price_after: Option<DateTime<FixedOffset>>,
     },
 }
 
+/// Markets accept SUI unless configured otherwise
+fn default_token() -> String {
+    "sui::sui::SUI".to_string()
+}
+
+/// Market types available in the protocol, without their configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MarketKind {
+    FixedPrice,
+    DutchAuction,
+}
+
 impl Market {
-    pub fn market_type(&self) -> &'static str {
+    pub fn kind(&self) -> MarketKind {
         match self {
-            Market::FixedPrice { .. } => "FixedPriceMarket",
-            Market::DutchAuction { .. } => "DutchAuctionMarket",
+            Market::FixedPrice { .. } => MarketKind::FixedPrice,
+            Market::DutchAuction { .. } => MarketKind::DutchAuction,
         }
     }
 
@@ -209,44 +615,147 @@ impl Market {
         }
     }
 
-    pub fn init(&self) -> String {
+    /// Returns the token, price, and whitelisting of the market, the price is
+    /// the fixed price or the reserve price depending on the market type
+    pub fn terms(&self) -> (&String, &u64, &bool) {
         match self {
             Market::FixedPrice {
                 token,
                 price,
                 is_whitelisted,
-            } => format!(
-                "
-        let inventory_id =
-            nft_protocol::listing::create_inventory(&mut listing, ctx);
-
-        nft_protocol::fixed_price::create_market_on_listing<{token}>(
-            &mut listing,
-            inventory_id,
-            {is_whitelisted},
-            {price},
-            ctx,
-        );
-",
-            ),
+            } => (token, price, is_whitelisted),
             Market::DutchAuction {
                 token,
                 reserve_price,
                 is_whitelisted,
-            } => format!(
-                "
-        let inventory_id =
-            nft_protocol::listing::create_inventory(&mut listing, ctx);
-
-        nft_protocol::dutch_auction::create_market_on_listing<{token}>(
-            &mut listing,
-            inventory_id,
-            {is_whitelisted},
-            {reserve_price},
-            ctx,
-        );
-",
-            ),
+                ..
+            } => (token, reserve_price, is_whitelisted),
         }
     }
+
+    /// Time after which a dutch auction may fall back to a fixed price venue
+    pub fn fixed_price_after(&self) -> Option<&DateTime<FixedOffset>> {
+        match self {
+            Market::FixedPrice { .. } => None,
+            Market::DutchAuction {
+                fixed_price_after, ..
+            } => fixed_price_after.as_ref(),
+        }
+    }
+
+    /// Removes the fixed price fallback of dutch auctions
+    pub fn clear_fallback(&mut self) {
+        if let Market::DutchAuction {
+            fixed_price_after, ..
+        } = self
+        {
+            *fixed_price_after = None;
+        }
+    }
+
+    /// Template context of the market
+    pub fn context(&self) -> Value {
+        let (token, price, is_whitelisted) = self.terms();
+
+        json!({
+            "module": self.market_module(),
+            "token": token,
+            "price": price,
+            "is_whitelisted": is_whitelisted,
+            "fallback_ms": self
+                .fixed_price_after()
+                .map(DateTime::timestamp_millis),
+        })
+    }
+}
+
+/// Sells NFTs sharing the metadata of the collection at a fixed price,
+/// without a supply limit, within a mint window after which minting closes
+/// permanently.
+///
+/// The window is compared against the start of the current epoch, hence
+/// minting may open up to an epoch after `start` and close up to an epoch
+/// after `end`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OpenEdition {
+    /// Receiver of the mint payments
+    #[serde(default = "default_admin")]
+    receiver: String,
+    /// Fully qualified fungible token in which price is denominated
+    #[serde(default = "default_token")]
+    token: String,
+    price: u64,
+    /// URL of the image of the edition
+    url: Box<str>,
+    /// ISO-8601 timestamp from which NFTs may be minted
+    start: DateTime<FixedOffset>,
+    /// ISO-8601 timestamp after which NFTs may no longer be minted
+    end: DateTime<FixedOffset>,
+}
+
+impl OpenEdition {
+    /// Checks that the mint window is representable in epoch milliseconds
+    /// and that it starts before it ends, and that the token is a valid type
+    /// path
+    pub fn validate(&self) -> Result<(), String> {
+        for time in [&self.start, &self.end] {
+            if time.timestamp_millis() < 0 {
+                return Err(format!(
+                    "Open edition mint time {time} must not be before the Unix epoch"
+                ));
+            }
+        }
+
+        if self.start >= self.end {
+            return Err(format!(
+                "Open edition mint start {} must be before its end {}",
+                self.start, self.end
+            ));
+        }
+
+        if !sanitize::is_type_path(&self.token) {
+            return Err(format!(
+                "Open edition token {:?} is not a fully qualified Move type such as sui::sui::SUI",
+                self.token
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the URL of the edition such that it can be sanitized, see
+    /// `Schema::sanitize`
+    pub fn url_mut(&mut self) -> &mut Box<str> {
+        &mut self.url
+    }
+
+    /// Lists the fields of the open edition under the given prefix, see
+    /// `Schema::field_values`
+    pub fn field_values(&self, prefix: &str) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        if self.receiver != default_admin() {
+            fields.push((format!("{prefix}.receiver"), self.receiver.clone()));
+        }
+
+        fields.push((format!("{prefix}.token"), self.token.clone()));
+        fields.push((format!("{prefix}.price"), self.price.to_string()));
+        fields.push((format!("{prefix}.url"), self.url.to_string()));
+        fields
+    }
+
+    /// Template context of the open edition
+    pub fn context(&self) -> Value {
+        json!({
+            "receiver": self.receiver,
+            "token": self.token,
+            "price": self.price,
+            "url": self.url,
+            "start_ms": self.start.timestamp_millis(),
+            "end_ms": self.end.timestamp_millis(),
+        })
+    }
 }
@@ -0,0 +1,21 @@
+//! Module exposing the generator to JavaScript through `wasm-bindgen`, such
+//! that web based configuration builders generate the same Move code as the
+//! CLI.
+use crate::schema::Schema;
+
+use wasm_bindgen::prelude::*;
+
+/// Generates the Move module from a configuration serialized as JSON.
+///
+/// The configuration is sanitized and validated like it is by the CLI, and
+/// any error is thrown as a JavaScript `Error`.
+#[wasm_bindgen(js_name = generateMoveFromJson)]
+pub fn generate_move_from_json(schema_json: &str) -> Result<String, JsError> {
+    // JSON is a subset of YAML, such that older configurations are migrated
+    let mut schema = Schema::from_reader(schema_json.as_bytes())?;
+    schema.prepare()?;
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output)?;
+    Ok(String::from_utf8(output)?)
+}
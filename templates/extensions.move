@@ -0,0 +1,8 @@
+{#
+    Custom Move code appended to the end of the collection module.
+
+    The built-in template is empty. Provide an `extensions.move` template in
+    the `--template-dir` directory to inject functions such as staking hooks
+    or custom domains. It is rendered with the same context as
+    `template.move`.
+#}
@@ -1,30 +1,136 @@
-module gutenberg::{module_name} {{
-    use std::string::{{Self, String}};
+module gutenberg::{{ module_name }} {
+    use std::string::{Self, String};
 
     use sui::url;
     use sui::balance;
     use sui::transfer;
-    use sui::tx_context::{{Self, TxContext}};
+    use sui::tx_context::{Self, TxContext};
 
     use nft_protocol::nft;
     use nft_protocol::tags;
     use nft_protocol::royalty;
     use nft_protocol::display;
     use nft_protocol::creators;
-    use nft_protocol::inventory::{{Self, Inventory}};
-    use nft_protocol::royalties::{{Self, TradePayment}};
-    use nft_protocol::collection::{{Self, Collection, MintCap}};
+    use nft_protocol::inventory::{Self, Inventory};
+    use nft_protocol::royalties::{Self, TradePayment};
+    use nft_protocol::collection::{Self, Collection, MintCap};
 
     /// One time witness is only instantiated in the init method
-    struct {witness} has drop {{}}
+    struct {{ witness }} has drop {}
 
     /// Can be used for authorization of other actions post-creation. It is
     /// vital that this struct is not freely given to any contract, because it
     /// serves as an auth token.
-    struct Witness has drop {{}}
+    struct Witness has drop {}
+{%- if cover_url or banner_url %}
 
-    fun init(witness: {witness}, ctx: &mut TxContext) {{
-        let (mint_cap, collection) = collection::create<{witness}>(
+    /// Collection domain holding the images representing the collection
+    struct CollectionImages has store {
+{%- if cover_url %}
+        cover_url: sui::url::Url,
+{%- endif %}
+{%- if banner_url %}
+        banner_url: sui::url::Url,
+{%- endif %}
+    }
+{%- endif %}
+{%- if transferability != "Transferable" %}
+
+    /// Wraps the NFT such that it can only be transferred by this module,
+    /// binding it to its receiver
+    struct SoulboundNft has key {
+        id: sui::object::UID,
+        nft: nft_protocol::nft::Nft<{{ witness }}>,
+{%- if transferability == "CreatorRevocable" %}
+        owner: address,
+        revoked: bool,
+{%- endif %}
+    }
+{%- endif %}
+{%- if burnable and burnable.redemption_event %}
+
+    /// Emitted when an NFT is burned to be redeemed
+    struct RedeemEvent has copy, drop {
+        nft_id: sui::object::ID,
+        owner: address,
+    }
+{%- endif %}
+{%- if events.mint %}
+
+    /// Emitted when an NFT is minted
+    struct MintEvent has copy, drop {
+        nft_id: sui::object::ID,
+{%- for field in events.mint.fields %}
+        {{ field.name }}: {{ field.type }},
+{%- endfor %}
+    }
+{%- endif %}
+{%- if burnable and events.burn %}
+
+    /// Emitted when an NFT is burned
+    struct BurnEvent has copy, drop {
+        nft_id: sui::object::ID,
+{%- for field in events.burn.fields %}
+        {{ field.name }}: {{ field.type }},
+{%- endfor %}
+    }
+{%- endif %}
+{%- if has_mutable_fields and events.field_update %}
+
+    /// Emitted when a mutable NFT field is updated
+    struct FieldUpdateEvent has copy, drop {
+        nft_id: sui::object::ID,
+        field: String,
+{%- for field in events.field_update.fields %}
+        {{ field.name }}: {{ field.type }},
+{%- endfor %}
+    }
+{%- endif %}
+{%- if fields %}
+
+    /// Custom fields stored on every NFT of the collection
+    struct Fields has store {
+{%- for field in fields %}
+        {{ field.name }}: {{ field.type }},
+{%- endfor %}
+    }
+{%- endif %}
+{%- if has_mutable_fields %}
+
+    /// Update of a mutable field of an NFT issued by the creator to the owner
+    /// of the NFT, who applies it
+    struct FieldUpdate<T: store> has key {
+        id: sui::object::UID,
+        nft_id: sui::object::ID,
+        field: String,
+        value: T,
+    }
+{%- endif %}
+{%- if has_fallbacks %}
+
+    /// Venues of a dutch auction and its fixed price fallback, recorded when
+    /// they are created in `init`, and the time in epoch milliseconds after
+    /// which the auction may fall back to a fixed price
+    struct AuctionFallback has key {
+        id: sui::object::UID,
+        auction_venue_id: sui::object::ID,
+        fixed_price_venue_id: sui::object::ID,
+        fallback_ms: u64,
+    }
+{%- endif %}
+{%- if open_edition %}
+
+    /// Sells NFTs sharing the collection metadata within the mint window,
+    /// until it is closed
+    struct OpenEdition has key {
+        id: sui::object::UID,
+        receiver: address,
+        closed: bool,
+    }
+{%- endif %}
+
+    fun init(witness: {{ witness }}, ctx: &mut TxContext) {
+        let (mint_cap, collection) = collection::create<{{ witness }}>(
             &witness,
             ctx,
         );
@@ -39,50 +145,151 @@ module gutenberg::{module_name} {{
         display::add_collection_display_domain(
             &mut collection,
             &mut mint_cap,
-            string::utf8(b"{name}"),
-            string::utf8(b"{description}"),
+            string::utf8(b"{{ name }}"),
+            string::utf8(b"{{ description }}"),
         );
 
         display::add_collection_url_domain(
             &mut collection,
             &mut mint_cap,
-            sui::url::new_unsafe_from_bytes(b"{url}"),
+            sui::url::new_unsafe_from_bytes(b"{{ url }}"),
+        );
+{%- if cover_url or banner_url %}
+
+        collection::add_domain(
+            &mut collection,
+            &mut mint_cap,
+            CollectionImages {
+{%- if cover_url %}
+                cover_url: sui::url::new_unsafe_from_bytes(b"{{ cover_url }}"),
+{%- endif %}
+{%- if banner_url %}
+                banner_url: sui::url::new_unsafe_from_bytes(b"{{ banner_url }}"),
+{%- endif %}
+            },
         );
+{%- endif %}
 
         display::add_collection_symbol_domain(
             &mut collection,
             &mut mint_cap,
-            string::utf8(b"{symbol}")
+            string::utf8(b"{{ symbol }}")
         );
 
         let royalty = royalty::new(ctx);
         royalty::add_proportional_royalty(
             &mut royalty,
-            nft_protocol::royalty_strategy_bps::new({royalty_fee_bps}),
+            nft_protocol::royalty_strategy_bps::new({{ royalty_fee_bps }}),
         );
         royalty::add_royalty_domain(&mut collection, &mut mint_cap, royalty);
 
-        {tags}
-{init_marketplace}{init_listings}{share_marketplace}
+        let tags = tags::empty(ctx);
+{%- for tag in tags %}
+        tags::add_tag(&mut tags, tags::{{ tag }}());
+{%- endfor %}
+{%- for tag in custom_tags %}
+        tags::add_tag(&mut tags, string::utf8(b"{{ tag }}"));
+{%- endfor %}
+        tags::add_collection_tag_domain(&mut collection, &mut mint_cap, tags);
+{%- if marketplace %}
+
+        let marketplace = nft_protocol::marketplace::new(
+            {{ marketplace.admin }},
+            {{ marketplace.receiver }},
+            nft_protocol::flat_fee::new({{ marketplace.fee_bps }}, ctx),
+            ctx,
+        );
+{%- endif %}
+{%- for listing in listings %}
+
+        let listing = nft_protocol::listing::new(
+            {{ listing.admin }},
+            {{ listing.receiver }},
+            ctx,
+        );
+{%- if listing.shared_inventory and listing.markets %}
+
+        let inventory_id =
+            nft_protocol::listing::create_inventory(&mut listing, ctx);
+{%- endif %}
+{%- for market in listing.markets %}
+{%- if not listing.shared_inventory %}
+
+        let inventory_id =
+            nft_protocol::listing::create_inventory(&mut listing, ctx);
+{%- endif %}
+
+{%- if market.fallback_ms %}
+
+        let auction_venue_id = nft_protocol::dutch_auction::create_venue<{{ market.token }}>(
+            &mut listing,
+            inventory_id,
+            {{ market.is_whitelisted }},
+            {{ market.price }},
+            ctx,
+        );
+
+        // Fixed price fallback of the auction, selling from its inventory
+        let fixed_price_venue_id = nft_protocol::fixed_price::create_venue<{{ market.token }}>(
+            &mut listing,
+            inventory_id,
+            {{ market.is_whitelisted }},
+            {{ market.price }},
+            ctx,
+        );
+
+        transfer::share_object(AuctionFallback {
+            id: sui::object::new(ctx),
+            auction_venue_id,
+            fixed_price_venue_id,
+            fallback_ms: {{ market.fallback_ms }},
+        });
+{%- else %}
+
+        nft_protocol::{{ market.module }}::create_market_on_listing<{{ market.token }}>(
+            &mut listing,
+            inventory_id,
+            {{ market.is_whitelisted }},
+            {{ market.price }},
+            ctx,
+        );
+{%- endif %}
+{%- endfor %}
+
+        transfer::share_object(listing);
+{%- endfor %}
+{%- if marketplace %}
+
+        transfer::share_object(marketplace);
+{%- endif %}
+{%- if open_edition %}
+
+        transfer::share_object(OpenEdition {
+            id: sui::object::new(ctx),
+            receiver: {{ open_edition.receiver }},
+            closed: false,
+        });
+{%- endif %}
+
         transfer::transfer(mint_cap, tx_context::sender(ctx));
         transfer::share_object(collection);
-    }}
+    }
 
     /// Calculates and transfers royalties to the `RoyaltyDomain`
     public entry fun collect_royalty<FT>(
-        payment: &mut TradePayment<{witness}, FT>,
-        collection: &mut Collection<{witness}>,
+        payment: &mut TradePayment<{{ witness }}, FT>,
+        collection: &mut Collection<{{ witness }}>,
         ctx: &mut TxContext,
-    ) {{
-        let b = royalties::balance_mut(Witness {{}}, payment);
+    ) {
+        let b = royalties::balance_mut(Witness {}, payment);
 
         let domain = royalty::royalty_domain(collection);
         let royalty_owed =
             royalty::calculate_proportional_royalty(domain, balance::value(b));
 
         royalty::collect_royalty(collection, b, royalty_owed);
-        royalties::transfer_remaining_to_beneficiary(Witness {{}}, payment, ctx);
-    }}
+        royalties::transfer_remaining_to_beneficiary(Witness {}, payment, ctx);
+    }
 
     public entry fun mint_nft(
         name: String,
@@ -90,11 +297,21 @@ module gutenberg::{module_name} {{
         url: vector<u8>,
         attribute_keys: vector<String>,
         attribute_values: vector<String>,
-        _mint_cap: &MintCap<{witness}>,
+{%- if nft_tags == "PerToken" %}
+        nft_tags: vector<String>,
+{%- endif %}
+{%- for field in fields %}
+        {{ field.name }}: {{ field.type }},
+{%- endfor %}
+        _mint_cap: &MintCap<{{ witness }}>,
+{%- if transferability == "Transferable" %}
         inventory: &mut Inventory,
+{%- else %}
+        receiver: address,
+{%- endif %}
         ctx: &mut TxContext,
-    ) {{
-        let nft = nft::new<{witness}>(tx_context::sender(ctx), ctx);
+    ) {
+        let nft = nft::new<{{ witness }}>(tx_context::sender(ctx), ctx);
 
         display::add_display_domain(
             &mut nft,
@@ -115,7 +332,406 @@ module gutenberg::{module_name} {{
             attribute_values,
             ctx,
         );
+{%- if nft_tags == "Inherit" %}
+
+        let tags = tags::empty(ctx);
+{%- for tag in tags %}
+        tags::add_tag(&mut tags, tags::{{ tag }}());
+{%- endfor %}
+{%- for tag in custom_tags %}
+        tags::add_tag(&mut tags, string::utf8(b"{{ tag }}"));
+{%- endfor %}
+        tags::add_tag_domain(&mut nft, tags, ctx);
+{%- elif nft_tags == "PerToken" %}
+
+        let tags = tags::empty(ctx);
+        std::vector::reverse(&mut nft_tags);
+        while (!std::vector::is_empty(&nft_tags)) {
+            tags::add_tag(&mut tags, std::vector::pop_back(&mut nft_tags));
+        };
+        tags::add_tag_domain(&mut nft, tags, ctx);
+{%- endif %}
+{%- if fields %}
+
+        nft::add_domain(
+            &mut nft,
+            Fields {
+{%- for field in fields %}
+                {{ field.name }},
+{%- endfor %}
+            },
+            ctx,
+        );
+{%- endif %}
+{%- if events.mint %}
+
+        sui::event::emit(MintEvent {
+            nft_id: sui::object::id(&nft),
+{%- for field in events.mint.fields %}
+            {{ field.name }},
+{%- endfor %}
+        });
+{%- endif %}
+{%- if transferability == "Transferable" %}
 
         inventory::deposit_nft(inventory, nft);
-    }}
-}}
+{%- elif transferability == "Soulbound" %}
+
+        transfer::transfer(
+            SoulboundNft { id: sui::object::new(ctx), nft },
+            receiver,
+        );
+{%- else %}
+
+        transfer::share_object(SoulboundNft {
+            id: sui::object::new(ctx),
+            nft,
+            owner: receiver,
+            revoked: false,
+        });
+{%- endif %}
+    }
+{%- if transferability != "Transferable" %}
+
+    /// Returns the NFT bound by the soulbound wrapper
+    public fun borrow_nft(
+        soulbound: &SoulboundNft,
+    ): &nft_protocol::nft::Nft<{{ witness }}> {
+        &soulbound.nft
+    }
+{%- endif %}
+{%- if collection_mutable.description %}
+
+    /// Updates the description of the collection
+    public entry fun update_collection_description(
+        _mint_cap: &MintCap<{{ witness }}>,
+        collection: &mut Collection<{{ witness }}>,
+        description: String,
+    ) {
+        let domain = collection::borrow_domain_mut<
+            {{ witness }}, display::DisplayDomain, Witness
+        >(Witness {}, collection);
+        display::set_description(domain, description);
+    }
+{%- endif %}
+{%- if collection_mutable.url %}
+
+    /// Updates the URL of the collection
+    public entry fun update_collection_url(
+        _mint_cap: &MintCap<{{ witness }}>,
+        collection: &mut Collection<{{ witness }}>,
+        url: vector<u8>,
+    ) {
+        let domain = collection::borrow_domain_mut<
+            {{ witness }}, display::UrlDomain, Witness
+        >(Witness {}, collection);
+        display::set_url(domain, url::new_unsafe_from_bytes(url));
+    }
+{%- endif %}
+{%- if collection_mutable.royalty_fee_bps %}
+
+    /// Royalty exceeds 100% of the sale price
+    const EInvalidRoyalty: u64 = 3;
+
+    /// Replaces the proportional royalty of the collection
+    public entry fun update_collection_royalty_fee_bps(
+        _mint_cap: &MintCap<{{ witness }}>,
+        collection: &mut Collection<{{ witness }}>,
+        royalty_fee_bps: u64,
+    ) {
+        assert!(royalty_fee_bps <= 10000, EInvalidRoyalty);
+
+        let domain = collection::borrow_domain_mut<
+            {{ witness }}, royalty::RoyaltyDomain, Witness
+        >(Witness {}, collection);
+        royalty::remove_proportional_royalty(domain);
+        royalty::add_proportional_royalty(
+            domain,
+            nft_protocol::royalty_strategy_bps::new(royalty_fee_bps),
+        );
+    }
+{%- endif %}
+{%- if burnable %}
+
+    /// Burns the NFT, only its owner may burn it
+    public entry fun burn_nft(
+{%- if transferability == "Soulbound" %}
+        soulbound: SoulboundNft,
+{%- else %}
+        nft: nft_protocol::nft::Nft<{{ witness }}>,
+{%- endif %}
+{%- if burnable.redemption_event %}
+        ctx: &mut TxContext,
+{%- endif %}
+    ) {
+{%- if transferability == "Soulbound" %}
+        let SoulboundNft { id, nft } = soulbound;
+        sui::object::delete(id);
+{%- endif %}
+{%- if transferability == "Soulbound" and (burnable.redemption_event or events.burn) %}
+{# Blank line between unwrapping and emitting events #}
+{%- endif %}
+{%- if burnable.redemption_event %}
+        sui::event::emit(RedeemEvent {
+            nft_id: sui::object::id(&nft),
+            owner: tx_context::sender(ctx),
+        });
+{%- endif %}
+{%- if events.burn %}
+        sui::event::emit(BurnEvent {
+            nft_id: sui::object::id(&nft),
+{%- for field in events.burn.fields %}
+            {{ field.name }}: nft::borrow_domain<{{ witness }}, Fields>(&nft).{{ field.name }},
+{%- endfor %}
+        });
+{%- endif %}
+{%- if fields %}
+
+        // Domains without drop must be unpacked before deleting the NFT
+        let Fields {
+{%- for field in fields %}
+            {{ field.name }}: _,
+{%- endfor %}
+        } = nft::remove_domain<{{ witness }}, Fields, Witness>(Witness {}, &mut nft);
+{%- endif %}
+
+        nft::delete(nft);
+    }
+{%- endif %}
+{%- if transferability == "CreatorRevocable" %}
+
+    /// Returns the address the NFT is bound to
+    public fun owner(soulbound: &SoulboundNft): address {
+        soulbound.owner
+    }
+
+    /// Returns whether the creator revoked the NFT
+    public fun is_revoked(soulbound: &SoulboundNft): bool {
+        soulbound.revoked
+    }
+
+    /// Revokes the NFT from its owner
+    public entry fun revoke_nft(
+        _mint_cap: &MintCap<{{ witness }}>,
+        soulbound: &mut SoulboundNft,
+    ) {
+        soulbound.revoked = true;
+    }
+{%- endif %}
+{%- if has_mutable_fields %}
+
+    /// Field update was issued for another NFT or field
+    const EInvalidFieldUpdate: u64 = 7;
+{%- endif %}
+{%- for field in fields %}
+
+    /// Returns the `{{ field.name }}` field of the NFT
+    public fun {{ field.name }}(
+        nft: &nft_protocol::nft::Nft<{{ witness }}>,
+    ): {% if field.type == "String" %}&{% endif %}{{ field.type }} {
+        {% if field.type == "String" %}&{% endif %}nft::borrow_domain<{{ witness }}, Fields>(nft).{{ field.name }}
+    }
+{%- if field.mutable %}
+
+    /// Issues an update of the `{{ field.name }}` field of the NFT to its owner,
+    /// who applies it using `apply_{{ field.name }}_update`
+    public entry fun update_{{ field.name }}(
+        _mint_cap: &MintCap<{{ witness }}>,
+        nft_id: sui::object::ID,
+        value: {{ field.type }},
+        owner: address,
+        ctx: &mut TxContext,
+    ) {
+        transfer::transfer(
+            FieldUpdate {
+                id: sui::object::new(ctx),
+                nft_id,
+                field: string::utf8(b"{{ field.name }}"),
+                value,
+            },
+            owner,
+        );
+    }
+
+    /// Applies an update of the `{{ field.name }}` field issued by the creator
+    public entry fun apply_{{ field.name }}_update(
+{%- if transferability == "Transferable" %}
+        nft: &mut nft_protocol::nft::Nft<{{ witness }}>,
+{%- else %}
+        soulbound: &mut SoulboundNft,
+{%- endif %}
+        update: FieldUpdate<{{ field.type }}>,
+    ) {
+{%- if transferability != "Transferable" %}
+        let nft = &mut soulbound.nft;
+{%- endif %}
+        let FieldUpdate { id, nft_id, field, value } = update;
+        sui::object::delete(id);
+        assert!(
+            nft_id == sui::object::id(nft)
+                && field == string::utf8(b"{{ field.name }}"),
+            EInvalidFieldUpdate,
+        );
+
+        let fields =
+            nft::borrow_domain_mut<{{ witness }}, Fields, Witness>(Witness {}, nft);
+        fields.{{ field.name }} = value;
+{%- if events.field_update %}
+
+        sui::event::emit(FieldUpdateEvent {
+            nft_id,
+            field,
+{%- for event_field in events.field_update.fields %}
+            {{ event_field.name }}: fields.{{ event_field.name }},
+{%- endfor %}
+        });
+{%- endif %}
+    }
+{%- endif %}
+{%- endfor %}
+{%- if has_schedules %}
+
+    /// Listing sale window has not started yet
+    const ESaleNotStarted: u64 = 1;
+
+    /// Listing sale window has already ended
+    const ESaleEnded: u64 = 2;
+{%- endif %}
+{%- for listing in listings %}
+{%- if listing.start_ms or listing.end_ms %}
+{%- if listing.start_ms %}
+
+    /// Start of the sale window of listing {{ listing.index }} in epoch milliseconds
+    const LISTING_{{ listing.index }}_START_MS: u64 = {{ listing.start_ms }};
+{%- endif %}
+{%- if listing.end_ms %}
+
+    /// End of the sale window of listing {{ listing.index }} in epoch milliseconds
+    const LISTING_{{ listing.index }}_END_MS: u64 = {{ listing.end_ms }};
+{%- endif %}
+
+    /// Opens a venue of listing {{ listing.index }}, may only be called within the
+    /// listing's sale window. The window is compared against the start of the
+    /// current epoch, and does not gate `nft_protocol::listing::sale_on`, nor
+    /// does it close open venues.
+    public entry fun sale_on_listing_{{ listing.index }}(
+        listing: &mut nft_protocol::listing::Listing,
+        venue_id: sui::object::ID,
+        ctx: &mut TxContext,
+    ) {
+        let now = tx_context::epoch_timestamp_ms(ctx);
+{%- if listing.start_ms %}
+        assert!(now >= LISTING_{{ listing.index }}_START_MS, ESaleNotStarted);
+{%- endif %}
+{%- if listing.end_ms %}
+        assert!(now < LISTING_{{ listing.index }}_END_MS, ESaleEnded);
+{%- endif %}
+
+        nft_protocol::listing::sale_on(listing, venue_id, ctx);
+    }
+{%- endif %}
+{%- endfor %}
+{%- if has_fallbacks %}
+
+    /// Auction has not reached the time of its fixed price fallback yet
+    const EAuctionNotEnded: u64 = 4;
+
+    /// Closes the dutch auction venue of the fallback and opens its fixed
+    /// price venue selling at the reserve price, may only be called by the
+    /// listing administrator once the current epoch started after the
+    /// fallback time
+    public entry fun fallback_auction(
+        fallback: &AuctionFallback,
+        listing: &mut nft_protocol::listing::Listing,
+        ctx: &mut TxContext,
+    ) {
+        let now = tx_context::epoch_timestamp_ms(ctx);
+        assert!(now >= fallback.fallback_ms, EAuctionNotEnded);
+
+        nft_protocol::listing::sale_off(listing, fallback.auction_venue_id, ctx);
+        nft_protocol::listing::sale_on(listing, fallback.fixed_price_venue_id, ctx);
+    }
+{%- endif %}
+{%- if open_edition %}
+
+    /// Open edition is closed or outside of its mint window
+    const EOpenEditionClosed: u64 = 5;
+
+    /// Payment does not match the price of the open edition
+    const EWrongPayment: u64 = 6;
+
+    /// Start of the open edition mint window in epoch milliseconds
+    const OPEN_EDITION_START_MS: u64 = {{ open_edition.start_ms }};
+
+    /// End of the open edition mint window in epoch milliseconds
+    const OPEN_EDITION_END_MS: u64 = {{ open_edition.end_ms }};
+
+    /// Price of an NFT of the open edition
+    const OPEN_EDITION_PRICE: u64 = {{ open_edition.price }};
+
+    /// Mints an NFT of the open edition to the sender against its price, may
+    /// only be called until the edition is closed while the start of the
+    /// current epoch is within the mint window
+    public entry fun mint_open_edition(
+        edition: &mut OpenEdition,
+        payment: sui::coin::Coin<{{ open_edition.token }}>,
+        ctx: &mut TxContext,
+    ) {
+        let now = tx_context::epoch_timestamp_ms(ctx);
+        assert!(
+            !edition.closed
+                && now >= OPEN_EDITION_START_MS
+                && now < OPEN_EDITION_END_MS,
+            EOpenEditionClosed,
+        );
+        assert!(sui::coin::value(&payment) == OPEN_EDITION_PRICE, EWrongPayment);
+        transfer::transfer(payment, edition.receiver);
+
+        let nft = nft::new<{{ witness }}>(tx_context::sender(ctx), ctx);
+
+        display::add_display_domain(
+            &mut nft,
+            string::utf8(b"{{ name }}"),
+            string::utf8(b"{{ description }}"),
+            ctx,
+        );
+
+        display::add_url_domain(
+            &mut nft,
+            url::new_unsafe_from_bytes(b"{{ open_edition.url }}"),
+            ctx,
+        );
+{%- if nft_tags == "Inherit" %}
+
+        let tags = tags::empty(ctx);
+{%- for tag in tags %}
+        tags::add_tag(&mut tags, tags::{{ tag }}());
+{%- endfor %}
+{%- for tag in custom_tags %}
+        tags::add_tag(&mut tags, string::utf8(b"{{ tag }}"));
+{%- endfor %}
+        tags::add_tag_domain(&mut nft, tags, ctx);
+{%- endif %}
+{%- if events.mint %}
+
+        sui::event::emit(MintEvent { nft_id: sui::object::id(&nft) });
+{%- endif %}
+
+        transfer::transfer(nft, tx_context::sender(ctx));
+    }
+
+    /// Closes the open edition permanently
+    public entry fun close_open_edition(
+        _mint_cap: &MintCap<{{ witness }}>,
+        edition: &mut OpenEdition,
+    ) {
+        edition.closed = true;
+    }
+{%- endif %}
+
+    #[test_only]
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init({{ witness }} {}, ctx);
+    }
+{%- filter trim_end %}{% include "extensions.move" %}{% endfilter %}
+}
@@ -0,0 +1,126 @@
+#[test_only]
+module gutenberg::{{ module_name }}_tests {
+    use std::string;
+
+    use sui::test_scenario;
+{%- if transferability == "Transferable" %}
+    use sui::transfer;
+{%- endif %}
+
+    use nft_protocol::royalty;
+{%- if transferability == "Transferable" %}
+    use nft_protocol::inventory;
+{%- endif %}
+    use nft_protocol::collection::{Collection, MintCap};
+
+    use gutenberg::{{ module_name }}::{Self, {{ witness }}{% if transferability != "Transferable" %}, SoulboundNft{% endif %}};
+
+    const CREATOR: address = @0xA1C05;
+
+    #[test]
+    fun it_inits_collection() {
+        let scenario = test_scenario::begin(CREATOR);
+
+        {{ module_name }}::init_for_testing(test_scenario::ctx(&mut scenario));
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        assert!(
+            test_scenario::has_most_recent_shared<Collection<{{ witness }}>>(),
+            0,
+        );
+        assert!(
+            test_scenario::has_most_recent_for_sender<MintCap<{{ witness }}>>(
+                &scenario,
+            ),
+            0,
+        );
+
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    fun it_calculates_royalty() {
+        let scenario = test_scenario::begin(CREATOR);
+
+        {{ module_name }}::init_for_testing(test_scenario::ctx(&mut scenario));
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        let collection = test_scenario::take_shared<Collection<{{ witness }}>>(
+            &scenario,
+        );
+
+        let domain = royalty::royalty_domain(&collection);
+        assert!(
+            royalty::calculate_proportional_royalty(domain, 10000) == {{ royalty_fee_bps }},
+            0,
+        );
+
+        test_scenario::return_shared(collection);
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    fun it_mints_nft() {
+        let scenario = test_scenario::begin(CREATOR);
+
+        {{ module_name }}::init_for_testing(test_scenario::ctx(&mut scenario));
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        let mint_cap = test_scenario::take_from_sender<MintCap<{{ witness }}>>(
+            &scenario,
+        );
+{%- if transferability == "Transferable" %}
+        let inventory = inventory::new(test_scenario::ctx(&mut scenario));
+{%- endif %}
+
+        {{ module_name }}::mint_nft(
+            string::utf8(b"Nft"),
+            string::utf8(b"Description of the NFT"),
+            b"https://originbyte.io/",
+            std::vector::singleton(string::utf8(b"rarity")),
+            std::vector::singleton(string::utf8(b"common")),
+{%- if nft_tags == "PerToken" %}
+            std::vector::singleton(string::utf8(b"Art")),
+{%- endif %}
+{%- for field in fields %}
+{%- if field.type == "bool" %}
+            false,
+{%- elif field.type == "address" %}
+            CREATOR,
+{%- elif field.type == "String" %}
+            string::utf8(b""),
+{%- else %}
+            0,
+{%- endif %}
+{%- endfor %}
+            &mint_cap,
+{%- if transferability == "Transferable" %}
+            &mut inventory,
+{%- else %}
+            CREATOR,
+{%- endif %}
+            test_scenario::ctx(&mut scenario),
+        );
+{%- if transferability == "Transferable" %}
+
+        assert!(!inventory::is_empty(&inventory), 0);
+        transfer::transfer(inventory, CREATOR);
+{%- endif %}
+
+        test_scenario::return_to_sender(&scenario, mint_cap);
+{%- if transferability == "Soulbound" %}
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        assert!(
+            test_scenario::has_most_recent_for_sender<SoulboundNft>(&scenario),
+            0,
+        );
+{%- elif transferability == "CreatorRevocable" %}
+        test_scenario::next_tx(&mut scenario, CREATOR);
+
+        assert!(test_scenario::has_most_recent_shared<SoulboundNft>(), 0);
+{%- endif %}
+
+        test_scenario::end(scenario);
+    }
+}
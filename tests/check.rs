@@ -0,0 +1,115 @@
+//! Integration tests checking that compiler errors reported by the Sui CLI
+//! are traced back to the configuration
+#![cfg(unix)]
+
+use gutenberg::prelude::*;
+
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+/// Template rendering a line only for the configured collection name
+const TEMPLATE: &str = r#"module gutenberg::{{ module_name }} {
+{%- if name == "Suitraders" %}
+    // Suitraders
+{%- endif %}
+    const ROYALTY_BPS: u64 = {{ royalty_fee_bps }};
+}
+"#;
+
+/// Fake Sui CLI reporting errors at the given lines of the generated package
+fn fake_sui(lines: &[usize]) -> String {
+    let mut script = "#!/bin/sh\n".to_string();
+    for line in lines {
+        script.push_str(&format!(
+            "echo \"error[E03002]: unbound module\"\n\
+             echo \"   ┌─ ./sources/suitraders.move:{line}:9\"\n"
+        ));
+    }
+    script.push_str("exit 1\n");
+    script
+}
+
+/// One-based number of the first line of the output after the given line
+/// which contains the pattern
+fn line(output: &str, after: usize, pattern: &str) -> usize {
+    output
+        .lines()
+        .skip(after)
+        .position(|line| line.contains(pattern))
+        .unwrap()
+        + after
+        + 1
+}
+
+#[test]
+fn diagnostics() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("check");
+    let package = dir.join("package");
+    fs::create_dir_all(package.join("sources")).unwrap();
+
+    let config = fs::File::open("./examples/suitraders.yaml").unwrap();
+    let schema = Schema::from_reader(config).unwrap();
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    fs::write(package.join("sources/suitraders.move"), &output).unwrap();
+
+    // Both markets are denominated in SUI, and the reserve price of the
+    // auction equals the royalty
+    let token = line(&output, 0, "fixed_price::create_market_on_listing<");
+    let auction = line(&output, 0, "dutch_auction::create_market_on_listing<");
+    let reserve_price = line(&output, auction, "100,");
+
+    let sui = dir.join("sui");
+    fs::write(&sui, fake_sui(&[token, reserve_price])).unwrap();
+    fs::set_permissions(&sui, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = env::var("PATH").unwrap_or_default();
+    env::set_var("PATH", format!("{}:{path}", dir.display()));
+
+    let diagnostics =
+        check::check(&schema, &template::builtin(), &package).unwrap();
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].message, "error[E03002]: unbound module");
+    assert_eq!(
+        diagnostics[0].fields,
+        vec!["Listings[0].markets[0].token = sui::sui::SUI"]
+    );
+    assert!(diagnostics[0]
+        .suggestion
+        .unwrap()
+        .starts_with("the market token type could not be found"));
+
+    assert_eq!(
+        diagnostics[1].fields,
+        vec!["Listings[0].markets[1].price = 100"]
+    );
+
+    // Lines following a block rendered depending on a field are still
+    // traced to their own fields when the block is omitted
+    let templates = dir.join("templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(templates.join("template.move"), TEMPLATE).unwrap();
+    let templates = template::from_dir(&templates).unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move_with(&templates, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    fs::write(package.join("sources/suitraders.move"), &output).unwrap();
+
+    let royalty = line(&output, 0, "ROYALTY_BPS");
+    fs::write(&sui, fake_sui(&[royalty])).unwrap();
+
+    let diagnostics = check::check(&schema, &templates, &package).unwrap();
+    assert_eq!(
+        diagnostics[0].fields,
+        vec!["Collection.royalty_fee_bps = 100"]
+    );
+
+    let err = GutenError::Compilation(diagnostics.len());
+    assert_eq!(err.kind(), ErrorKind::Compilation);
+    assert_eq!(err.kind().exit_code(), 4);
+}
@@ -0,0 +1,30 @@
+//! Integration tests checking that platform constraints are enforced
+
+use gutenberg::prelude::*;
+
+use std::fs::File;
+
+#[test]
+fn constraints() {
+    let config = File::open("./examples/suitraders.yaml").unwrap();
+    let schema = Schema::from_reader(config).unwrap();
+
+    let constraints = Constraints::from_reader(
+        "
+max_royalty_fee_bps: 1000
+allowed_markets: [FixedPrice, DutchAuction]
+soulbound_tags: [Ticket]
+"
+        .as_bytes(),
+    )
+    .unwrap();
+    constraints.enforce(&schema).unwrap();
+
+    let constraints =
+        Constraints::from_reader("allowed_markets: [FixedPrice]".as_bytes())
+            .unwrap();
+    assert!(matches!(
+        constraints.enforce(&schema),
+        Err(GutenError::InvalidConfig(_))
+    ));
+}
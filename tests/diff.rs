@@ -0,0 +1,37 @@
+//! Integration tests checking that configurations are compared field by
+//! field
+
+use gutenberg::prelude::*;
+
+use std::fs::File;
+
+#[test]
+fn diff() {
+    let config = File::open("./examples/suitraders.yaml").unwrap();
+    let schema = Schema::from_reader(config).unwrap();
+
+    // Identical configurations have no changes
+    assert!(diff::diff(&schema, &schema).unwrap().is_empty());
+
+    let mut other = schema.clone();
+    other.collection.royalty_fee_bps = "200".into();
+    other.marketplace = None;
+    other.listings.as_mut().unwrap().pop();
+
+    let changes = diff::diff(&schema, &other).unwrap();
+
+    // Changes are tagged by their kind when printed with `--json`
+    let json = serde_json::to_value(&changes[0]).unwrap();
+    assert_eq!(json["change"], "changed");
+    assert_eq!(json["path"], "Collection.royalty_fee_bps");
+
+    let changes = changes.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(changes.len(), 3);
+    assert_eq!(
+        changes[0],
+        r#"~ Collection.royalty_fee_bps: "100" -> "200""#
+    );
+    assert!(changes[1].starts_with("- Marketplace: "));
+    assert!(changes[2].starts_with("- Listings[0]: "));
+}
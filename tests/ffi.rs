@@ -0,0 +1,42 @@
+//! Integration tests checking that the C ABI reports results and errors as
+//! JSON
+
+use gutenberg::ffi::*;
+
+use serde_json::Value;
+
+use std::ffi::{CStr, CString};
+use std::fs;
+
+/// Calls the function with the config and parses its JSON response
+fn call(
+    f: unsafe extern "C" fn(*const std::ffi::c_char) -> *mut std::ffi::c_char,
+    config: &str,
+) -> Value {
+    let config = CString::new(config).unwrap();
+    unsafe {
+        let response = f(config.as_ptr());
+        let value = serde_json::from_slice(CStr::from_ptr(response).to_bytes())
+            .unwrap();
+        gutenberg_free(response);
+        value
+    }
+}
+
+#[test]
+fn generate() {
+    let config = fs::read_to_string("./examples/suimarines.yaml").unwrap();
+    let expected =
+        fs::read_to_string("./examples/packages/sources/suimarines.move")
+            .unwrap();
+
+    let response = call(gutenberg_generate, &config);
+    assert_eq!(response["module_name"], "suimarines");
+    assert_eq!(response["module"], expected);
+
+    let response = call(gutenberg_validate, &config);
+    assert_eq!(response["modifications"], Value::Array(Vec::new()));
+
+    let response = call(gutenberg_validate, "NftType: Classic");
+    assert_eq!(response["kind"], "validation");
+}
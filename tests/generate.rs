@@ -1,7 +1,13 @@
 //! Integration tests directly check the generated examples in the parent directory
 
+use gutenberg::err::GutenError;
+use gutenberg::format::Format;
+use gutenberg::report;
 use gutenberg::schema::Schema;
+use gutenberg::template;
+use gutenberg::types::{NftTags, Tag};
 use std::fs::{self, File};
+use std::path::Path;
 
 /// Check that all examples have correct schema
 #[test]
@@ -21,11 +27,540 @@ fn example_schema() {
 #[test]
 fn suimarines() {
     assert_equal("suimarines.yaml", "suimarines.move");
+    assert_tests_equal("suimarines.yaml", "suimarines_tests.move");
 }
 
 #[test]
 fn suitraders() {
     assert_equal("suitraders.yaml", "suitraders.move");
+    assert_tests_equal("suitraders.yaml", "suitraders_tests.move");
+}
+
+/// Check that templates loaded from a directory are used in place of the
+/// built-in ones
+#[test]
+fn template_dir() {
+    let (config, expected) = setup("suimarines.yaml", "suimarines.move");
+    let templates = template::from_dir(Path::new("./templates")).unwrap();
+
+    let mut output = Vec::new();
+    assert_schema(config)
+        .write_move_with(&templates, &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    pretty_assertions::assert_eq!(output, expected);
+}
+
+/// Check that templates missing from the directory fall back to the built-in
+/// ones and that extensions are appended to the module
+#[test]
+fn template_extensions() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("extensions");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(template::EXTENSIONS),
+        "\n\n    public fun stake() {}\n",
+    )
+    .unwrap();
+    // Files which are not templates are ignored
+    fs::write(dir.join(".DS_Store"), [0xff, 0xfe, 0x00]).unwrap();
+    fs::write(dir.join("extensions.move.swp"), [0xff, 0xfe, 0x00]).unwrap();
+
+    let (config, expected) = setup("suimarines.yaml", "suimarines.move");
+    let templates = template::from_dir(&dir).unwrap();
+
+    let mut output = Vec::new();
+    assert_schema(config)
+        .write_move_with(&templates, &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    let expected = expected
+        .replace("    }\n}\n", "    }\n\n    public fun stake() {}\n}\n");
+    pretty_assertions::assert_eq!(output, expected);
+}
+
+/// Check that configurations converted to TOML generate the same code
+#[test]
+fn toml() {
+    let (config, expected) = setup("suitraders.yaml", "suitraders.move");
+
+    let config = Format::Yaml.read(config).unwrap();
+    let toml = Format::Toml.write(&config).unwrap();
+    assert!(toml.contains("[Listings.markets.FixedPrice]"));
+
+    let schema =
+        Schema::from_reader_with(Format::Toml, toml.as_bytes()).unwrap();
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    pretty_assertions::assert_eq!(output, expected);
+}
+
+/// Check that schemas built programmatically generate the same code as
+/// their configuration
+#[test]
+fn builder() {
+    let (config, expected) = setup("suimarines.yaml", "suimarines.move");
+
+    let builder = Schema::builder()
+        .description("A unique NFT collection of Suimarines on Sui")
+        .symbol("SUIM")
+        .tag(Tag::Art)
+        .royalties("100")
+        .url("https://originbyte.io/");
+
+    let (schema, modifications) =
+        builder.clone().name("Suimarines").build().unwrap();
+    assert!(modifications.is_empty());
+    assert_eq!(schema, assert_schema(config));
+
+    // Sanitized like the configuration file
+    let (sanitized, modifications) =
+        builder.name("Suimarines\n").build().unwrap();
+    assert_eq!(modifications.len(), 1);
+    assert_eq!(sanitized, schema);
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    pretty_assertions::assert_eq!(output, expected);
+
+    let err = Schema::builder().name("Suimarines").build().unwrap_err();
+    assert!(matches!(err, GutenError::InvalidConfig(_)));
+}
+
+/// Check that NFTs carry the collection tags or the tags passed at mint
+#[test]
+fn nft_tags() {
+    let (config, _) = setup("suimarines.yaml", "suimarines.move");
+    let mut schema = assert_schema(config);
+
+    schema.nft_tags = NftTags::Inherit;
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(
+        output
+            .matches("tags::add_tag(&mut tags, tags::art());")
+            .count(),
+        2
+    );
+    assert!(output.contains("tags::add_tag_domain(&mut nft, tags, ctx);"));
+    assert!(!output.contains("nft_tags: vector<String>"));
+
+    schema.nft_tags = NftTags::PerToken;
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(
+        output
+            .matches("tags::add_tag(&mut tags, tags::art());")
+            .count(),
+        1
+    );
+    assert!(output.contains("nft_tags: vector<String>,"));
+    assert!(output.contains("tags::add_tag_domain(&mut nft, tags, ctx);"));
+}
+
+/// Check that markets of a listing with a shared inventory sell from the
+/// same inventory
+#[test]
+fn shared_inventory() {
+    let config = fs::read_to_string("./examples/suitraders.yaml")
+        .unwrap()
+        .replace("  - receiver:", "  - shared_inventory: true\n    receiver:");
+    let schema = Schema::from_reader(config.as_bytes()).unwrap();
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(output.matches("listing::create_inventory").count(), 1);
+    assert_eq!(output.matches("create_market_on_listing").count(), 2);
+}
+
+/// Check that collection update functions are only generated for the
+/// mutable collection fields
+#[test]
+fn collection_mutability() {
+    let functions = [
+        "public entry fun update_collection_description(",
+        "public entry fun update_collection_url(",
+        "public entry fun update_collection_royalty_fee_bps(",
+    ];
+
+    let output = generate("");
+    for function in functions {
+        assert!(!output.contains(function));
+    }
+
+    let output = generate(
+        r#"
+  mutable:
+    description: true
+    url: true
+    royalty_fee_bps: true
+"#,
+    );
+    for function in functions {
+        assert!(output.contains(function));
+    }
+
+    let output = generate("\n  mutable:\n    url: true\n");
+    assert!(!output.contains(functions[0]));
+    assert!(output.contains(functions[1]));
+    assert!(!output.contains(functions[2]));
+}
+
+/// Check that listings with a sale window open their venues only within it
+#[test]
+fn listing_schedule() {
+    let output = generate(
+        r#"
+Listings:
+  - start: "2023-01-10T12:00:00Z"
+    markets: []
+  - start: "2023-01-10T12:00:00Z"
+    end: "2023-01-20T12:00:00Z"
+    markets: []
+"#,
+    );
+
+    assert!(output.contains("const LISTING_1_START_MS: u64 = 1673352000000;"));
+    assert!(!output.contains("LISTING_1_END_MS"));
+    assert!(output.contains("const LISTING_2_START_MS: u64 = 1673352000000;"));
+    assert!(output.contains("const LISTING_2_END_MS: u64 = 1674216000000;"));
+    assert!(output.contains(
+        "assert!(now >= LISTING_2_START_MS, ESaleNotStarted);
+        assert!(now < LISTING_2_END_MS, ESaleEnded);
+
+        nft_protocol::listing::sale_on(listing, venue_id, ctx);"
+    ));
+    assert!(output.contains("public entry fun sale_on_listing_1("));
+    assert!(output.contains("public entry fun sale_on_listing_2("));
+}
+
+/// Check that auctions with a fallback create a fixed price venue on their
+/// inventory and a function switching to it
+#[test]
+fn auction_fallback() {
+    let config = fs::read_to_string("./examples/suitraders.yaml")
+        .unwrap()
+        .replace(
+            "        reserve_price: 100\n",
+            "        reserve_price: 100\n        fixed_price_after: \"2023-01-12T12:00:00Z\"\n",
+        );
+    let schema = Schema::from_reader(config.as_bytes()).unwrap();
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(output.matches("listing::create_inventory").count(), 2);
+    assert!(output.contains("fixed_price::create_market_on_listing"));
+    assert!(output.contains(
+        "let auction_venue_id = nft_protocol::dutch_auction::create_venue<sui::sui::SUI>("
+    ));
+    assert!(output.contains(
+        "let fixed_price_venue_id = nft_protocol::fixed_price::create_venue<sui::sui::SUI>("
+    ));
+    assert!(output.contains(
+        "transfer::share_object(AuctionFallback {
+            id: sui::object::new(ctx),
+            auction_venue_id,
+            fixed_price_venue_id,
+            fallback_ms: 1673524800000,
+        });"
+    ));
+    assert!(output.contains(
+        "nft_protocol::listing::sale_off(listing, fallback.auction_venue_id, ctx);"
+    ));
+    assert!(!output.contains("dutch_auction::create_market_on_listing"));
+}
+
+/// Check that open editions generate a time-bounded mint function and a
+/// function closing the edition
+#[test]
+fn open_edition() {
+    let config = fs::read_to_string("./examples/suimarines.yaml").unwrap()
+        + r#"
+OpenEdition:
+  price: 100
+  url: "https://originbyte.io/edition.png"
+  start: "2023-01-10T12:00:00Z"
+  end: "2023-01-20T12:00:00Z"
+"#;
+    let schema = Schema::from_reader(config.as_bytes()).unwrap();
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("struct OpenEdition has key {"));
+    assert!(
+        output.contains("const OPEN_EDITION_START_MS: u64 = 1673352000000;")
+    );
+    assert!(output.contains("const OPEN_EDITION_END_MS: u64 = 1674216000000;"));
+    assert!(output.contains("payment: sui::coin::Coin<sui::sui::SUI>,"));
+    assert!(output.contains("public entry fun mint_open_edition("));
+    assert!(output.contains("public entry fun close_open_edition("));
+}
+
+/// Check that custom fields are stored on the NFT, passed to `mint_nft`, and
+/// readable through accessors
+#[test]
+fn fields() {
+    let output = generate(
+        r#"
+Fields:
+  - name: level
+    type: u64
+  - name: animation_url
+    type: String
+"#,
+    );
+
+    assert!(output.contains(
+        "struct Fields has store {
+        level: u64,
+        animation_url: String,
+    }"
+    ));
+    assert!(output.contains(
+        "        attribute_values: vector<String>,
+        level: u64,
+        animation_url: String,
+        _mint_cap: &MintCap<SUIMARINES>,"
+    ));
+    assert!(output.contains(
+        "            Fields {
+                level,
+                animation_url,
+            },"
+    ));
+    assert!(output.contains("public fun level("));
+    assert!(output.contains("public fun animation_url("));
+    assert!(!output.contains("public entry fun update_level("));
+
+    assert!(!generate("").contains("struct Fields"));
+}
+
+/// Check that soulbound NFTs are wrapped and minted to their receiver, and
+/// that only creator revocable NFTs can be revoked
+#[test]
+fn transferability() {
+    let output = generate("\nTransferability: Soulbound\n");
+
+    assert!(output.contains("struct SoulboundNft has key {"));
+    assert!(output
+        .contains("        receiver: address,\n        ctx: &mut TxContext,"));
+    assert!(output.contains(
+        "transfer::transfer(
+            SoulboundNft { id: sui::object::new(ctx), nft },
+            receiver,
+        );"
+    ));
+    assert!(output.contains("public fun borrow_nft("));
+    assert!(!output.contains("inventory::deposit_nft"));
+    assert!(!output.contains("public entry fun revoke_nft("));
+
+    let output = generate("\nTransferability: CreatorRevocable\n");
+
+    assert!(output.contains("transfer::share_object(SoulboundNft {"));
+    assert!(output.contains("        owner: address,\n        revoked: bool,"));
+    assert!(output.contains("public entry fun revoke_nft("));
+    assert!(output.contains("public fun is_revoked("));
+
+    let output = generate("");
+    assert!(output.contains("inventory::deposit_nft(inventory, nft);"));
+    assert!(!output.contains("SoulboundNft"));
+}
+
+/// Check that burnable NFTs can be burned by their owner, optionally
+/// emitting a redemption event
+#[test]
+fn burnable() {
+    assert!(!generate("").contains("burn_nft"));
+
+    let output = generate("\nBurnable: {}\n");
+    assert!(output.contains(
+        "public entry fun burn_nft(
+        nft: nft_protocol::nft::Nft<SUIMARINES>,
+    ) {"
+    ));
+    assert!(output.contains("nft::delete(nft);"));
+    assert!(!output.contains("RedeemEvent"));
+
+    let output = generate("\nBurnable:\n  redemption_event: true\n");
+    assert!(output.contains("struct RedeemEvent has copy, drop {"));
+    assert!(output.contains(
+        "sui::event::emit(RedeemEvent {
+            nft_id: sui::object::id(&nft),
+            owner: tx_context::sender(ctx),
+        });"
+    ));
+
+    // The custom fields are removed before deleting the NFT
+    let output = generate(
+        r#"
+Fields:
+  - name: level
+    type: u64
+  - name: animation_url
+    type: String
+Burnable: {}
+"#,
+    );
+    assert!(output.contains(
+        "let Fields {
+            level: _,
+            animation_url: _,
+        } = nft::remove_domain<SUIMARINES, Fields, Witness>(Witness {}, &mut nft);
+
+        nft::delete(nft);"
+    ));
+
+    let output = generate("\nTransferability: Soulbound\nBurnable: {}\n");
+    assert!(output.contains(
+        "let SoulboundNft { id, nft } = soulbound;
+        sui::object::delete(id);"
+    ));
+}
+
+/// Check that events carry the NFT ID and the configured custom fields
+#[test]
+fn events() {
+    let output = generate(
+        r#"
+Fields:
+  - name: level
+    type: u64
+    mutable: true
+Events:
+  mint:
+    fields: [level]
+  field_update:
+    fields: [level]
+"#,
+    );
+
+    assert!(output.contains(
+        "struct MintEvent has copy, drop {
+        nft_id: sui::object::ID,
+        level: u64,
+    }"
+    ));
+    assert!(output.contains("public entry fun update_level("));
+    assert!(output.contains(
+        "public entry fun apply_level_update(
+        nft: &mut nft_protocol::nft::Nft<SUIMARINES>,
+        update: FieldUpdate<u64>,
+    ) {"
+    ));
+    assert!(output.contains(
+        "struct FieldUpdateEvent has copy, drop {
+        nft_id: sui::object::ID,
+        field: String,
+        level: u64,
+    }"
+    ));
+}
+
+/// Check that the generated mint test passes a value for every parameter of
+/// `mint_nft`
+#[test]
+fn mint_test() {
+    let config = fs::read_to_string("./examples/suimarines.yaml").unwrap()
+        + r#"
+Transferability: Soulbound
+NftTags: PerToken
+Fields:
+  - name: level
+    type: u64
+  - name: active
+    type: bool
+"#;
+    let schema = Schema::from_reader(config.as_bytes()).unwrap();
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move_tests(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains(
+        "            std::vector::singleton(string::utf8(b\"Art\")),
+            0,
+            false,
+            &mint_cap,
+            CREATOR,"
+    ));
+    assert!(output.contains("has_most_recent_for_sender<SoulboundNft>"));
+    assert!(!output.contains("inventory::new"));
+}
+
+/// Check that template directories declaring incompatible requirements are
+/// rejected
+#[test]
+fn template_requirements() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("requirements");
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(
+        dir.join(template::REQUIREMENTS),
+        "nft_protocol: \"^0.16\"\n",
+    )
+    .unwrap();
+    template::from_dir(&dir).unwrap();
+
+    fs::write(
+        dir.join(template::REQUIREMENTS),
+        "nft_protocol: \">=0.17\"\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        template::from_dir(&dir),
+        Err(GutenError::Incompatible(_))
+    ));
+}
+
+/// Check that the complexity report lists the enabled features
+#[test]
+fn report() {
+    let config = File::open("./examples/suitraders.yaml").unwrap();
+    let report = report::report(&assert_schema(config)).unwrap();
+
+    let features = report
+        .features
+        .iter()
+        .map(|feature| feature.name)
+        .collect::<Vec<_>>();
+    assert_eq!(features, vec!["Marketplace", "Listings"]);
+
+    // Marketplace and listings are created within `init`
+    assert!(report.features.iter().all(|feature| feature.functions == 0));
+}
+
+/// Generates the Move module of the Suimarines example extended by the config
+fn generate(config: &str) -> String {
+    let config =
+        fs::read_to_string("./examples/suimarines.yaml").unwrap() + config;
+    let mut schema = Schema::from_reader(config.as_bytes()).unwrap();
+    schema.prepare().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    String::from_utf8(output).unwrap()
 }
 
 fn setup(config: &str, expected: &str) -> (File, String) {
@@ -39,7 +574,9 @@ fn setup(config: &str, expected: &str) -> (File, String) {
 
 /// Asserts that the config file has correct schema
 fn assert_schema(config: File) -> Schema {
-    serde_yaml::from_reader::<_, Schema>(config).unwrap()
+    let schema = Schema::from_reader(config).unwrap();
+    schema.validate().unwrap();
+    schema
 }
 
 /// Asserts that the generated file matches the expected output
@@ -52,3 +589,17 @@ fn assert_equal(config: &str, expected: &str) {
 
     pretty_assertions::assert_eq!(output, expected);
 }
+
+/// Asserts that the generated Move tests match the expected output
+fn assert_tests_equal(config: &str, expected: &str) {
+    let config = File::open(format!("./examples/{config}")).unwrap();
+    let expected =
+        fs::read_to_string(format!("./examples/packages/tests/{expected}"))
+            .unwrap();
+
+    let mut output = Vec::new();
+    assert_schema(config).write_move_tests(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    pretty_assertions::assert_eq!(output, expected);
+}
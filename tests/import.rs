@@ -0,0 +1,48 @@
+//! Integration tests checking that configurations of other NFT tooling are
+//! imported into valid configurations
+
+use gutenberg::prelude::*;
+
+#[test]
+fn candy_machine() {
+    let config = r#"{
+        "price": 1.5,
+        "number": 10000,
+        "symbol": "SUIM",
+        "sellerFeeBasisPoints": 500,
+        "goLiveDate": "25 Dec 2021 00:00:00 GMT",
+        "creators": [{ "address": "BPr1...", "share": 100 }]
+    }"#;
+
+    let import =
+        import::candy_machine(config.as_bytes(), "Suimarines").unwrap();
+    assert_eq!(import.skipped.len(), 3);
+
+    let schema: Schema = serde_yaml::from_value(import.config).unwrap();
+    assert_eq!(&*schema.collection.name, "Suimarines");
+    assert_eq!(&*schema.collection.symbol, "SUIM");
+    assert_eq!(schema.collection.royalty_bps(), Ok(500));
+    assert_eq!(
+        schema.listings.unwrap()[0].start_ms(),
+        Some(1_640_390_400_000)
+    );
+
+    // Sugar configures the go-live date as a guard
+    let config = r#"{
+        "symbol": "SUIM",
+        "sellerFeeBasisPoints": 250,
+        "guards": {
+            "default": { "startDate": { "date": "2022-10-20T00:00:00Z" } }
+        }
+    }"#;
+
+    let import =
+        import::candy_machine(config.as_bytes(), "Suimarines").unwrap();
+    assert!(import.skipped.is_empty());
+
+    let schema: Schema = serde_yaml::from_value(import.config).unwrap();
+    assert_eq!(
+        schema.listings.unwrap()[0].start_ms(),
+        Some(1_666_224_000_000)
+    );
+}
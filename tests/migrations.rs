@@ -0,0 +1,46 @@
+//! Integration tests checking that configurations written for older schema
+//! versions are upgraded to the current layout
+
+use gutenberg::prelude::*;
+
+use serde_yaml::Value;
+
+#[test]
+fn unversioned() {
+    let mut config: Value = serde_yaml::from_str("NftType: Classic").unwrap();
+
+    assert!(migrations::migrate(&mut config).unwrap());
+    assert_eq!(
+        migrations::version(&config).unwrap(),
+        migrations::CURRENT_VERSION
+    );
+
+    // Migrating an up to date configuration is a no-op
+    assert!(!migrations::migrate(&mut config).unwrap());
+}
+
+#[test]
+fn unsupported_version() {
+    let mut config: Value = serde_yaml::from_str("Version: 1000").unwrap();
+
+    assert!(matches!(
+        migrations::migrate(&mut config),
+        Err(GutenError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn annotate() {
+    let config = "Version: 1\nCollection:\n  tags: []\nFields:\n- name: level\n  type: u64\n- name: xp\n  type: u64\n";
+
+    let annotated = annotate::annotate(config);
+    assert!(annotated.contains(
+        "\n  # Categories of the collection, such as Art or Music, or custom tags\n"
+    ));
+    // Fields of list items are only described on their first occurrence
+    assert_eq!(annotated.matches("# Move type of the field, one of: bool, u8, u16, u32, u64, u128, address, String\n").count(), 1);
+
+    // Annotations are comments which do not change the configuration
+    let value: Value = serde_yaml::from_str(&annotated).unwrap();
+    assert_eq!(value, serde_yaml::from_str::<Value>(config).unwrap());
+}
@@ -0,0 +1,237 @@
+//! Property tests checking that arbitrary valid configurations survive
+//! serialization round trips and generate identical Move code
+
+use gutenberg::prelude::*;
+
+use chrono::{DateTime, FixedOffset};
+use proptest::prelude::*;
+
+/// Quotes a string as a YAML double quoted scalar
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+fn timestamp() -> impl Strategy<Value = (i64, i32)> {
+    (0..4_000_000_000i64, -12..=14i32)
+}
+
+fn rfc3339((secs, offset_hours): (i64, i32)) -> String {
+    let offset = FixedOffset::east_opt(offset_hours * 3600).unwrap();
+    DateTime::from_timestamp(secs, 0)
+        .unwrap()
+        .with_timezone(&offset)
+        .to_rfc3339()
+}
+
+fn market() -> impl Strategy<Value = String> {
+    (
+        any::<bool>(),
+        prop::option::of(prop::sample::select(vec![
+            "sui::sui::SUI",
+            "0x2::sui::SUI",
+            "0xab::gold::GOLD<0x2::sui::SUI, u64>",
+        ])),
+        any::<u64>(),
+        any::<bool>(),
+    )
+        .prop_map(|(fixed, token, price, is_whitelisted)| {
+            let (kind, price_key) = if fixed {
+                ("FixedPrice", "price")
+            } else {
+                ("DutchAuction", "reserve_price")
+            };
+            let token = token
+                .map(|token| format!("        token: {}\n", quote(token)))
+                .unwrap_or_default();
+
+            format!(
+                "      - !{kind}\n{token}        {price_key}: {price}\n        is_whitelisted: {is_whitelisted}\n"
+            )
+        })
+}
+
+fn listing() -> impl Strategy<Value = String> {
+    (
+        prop::option::of("0x[0-9a-f]{40}"),
+        prop::option::of(timestamp()),
+        prop::option::of(1..10_000_000i64),
+        prop::collection::vec(market(), 0..3),
+    )
+        .prop_map(|(admin, start, duration, markets)| {
+            let mut listing = String::from("  - markets:");
+            if markets.is_empty() {
+                listing.push_str(" []\n");
+            } else {
+                listing.push('\n');
+                markets.iter().for_each(|m| listing.push_str(m));
+            }
+            if let Some(admin) = admin {
+                listing.push_str(&format!("    admin: \"{admin}\"\n"));
+            }
+            if let Some((secs, offset)) = start {
+                listing.push_str(&format!(
+                    "    start: {}\n",
+                    quote(&rfc3339((secs, offset)))
+                ));
+                if let Some(duration) = duration {
+                    listing.push_str(&format!(
+                        "    end: {}\n",
+                        quote(&rfc3339((secs + duration, offset)))
+                    ));
+                }
+            }
+            listing
+        })
+}
+
+prop_compose! {
+    /// Generates configurations, which may still be semantically invalid
+    fn config()(
+        name in "[A-Z][a-z]{2,12}( [A-Z][a-z]{2,8})?",
+        description in "[ -~]{0,60}",
+        symbol in "[A-Z]{1,8}",
+        tags in prop::sample::subsequence(vec!["Art", "Music", "GameAsset", "Surfing"], 0..=3),
+        royalty_fee_bps in 0..=10_000u16,
+        url in "https://[a-z]{1,10}\\.io/",
+        module_name in prop::option::of("[a-z][a-z0-9_]{2,10}"),
+        mutable in any::<(bool, bool, bool)>(),
+        transferability in prop::sample::select(
+            vec!["Transferable", "Soulbound", "CreatorRevocable"]
+        ),
+        nft_tags in prop::sample::select(vec!["None", "Inherit", "PerToken"]),
+        burnable in prop::option::of(any::<bool>()),
+        fields in prop::collection::btree_map(
+            "f_[a-z0-9_]{1,6}",
+            (
+                prop::sample::select(vec![
+                    "bool", "u8", "u16", "u32", "u64", "u128", "address",
+                    "String",
+                ]),
+                any::<bool>(),
+            ),
+            0..4,
+        ),
+        events in any::<(Option<bool>, Option<bool>, Option<bool>)>(),
+        marketplace in any::<bool>(),
+        listings in prop::option::of(prop::collection::vec(listing(), 0..3)),
+        strict in any::<bool>(),
+    ) -> String {
+        let mut config = format!(
+            r#"Version: 1
+NftType: Classic
+Collection:
+  name: {name}
+  description: {description}
+  symbol: {symbol}
+  tags: [{tags}]
+  royalty_fee_bps: "{royalty_fee_bps}"
+  url: {url}
+{module_name}  mutable:
+    description: {}
+    url: {}
+    royalty_fee_bps: {}
+Transferability: {transferability}
+NftTags: {nft_tags}
+Sanitization: {sanitization}
+"#,
+            mutable.0,
+            mutable.1,
+            mutable.2,
+            name = quote(&name),
+            description = quote(&description),
+            tags = tags.join(", "),
+            url = quote(&url),
+            module_name = module_name
+                .map(|module_name| format!("  module_name: {module_name}\n"))
+                .unwrap_or_default(),
+            sanitization = if strict { "Strict" } else { "Lenient" },
+        );
+
+        if let Some(redemption_event) = burnable {
+            config.push_str(&format!(
+                "Burnable:\n  redemption_event: {redemption_event}\n"
+            ));
+        }
+
+        config.push_str("Fields:");
+        if fields.is_empty() {
+            config.push_str(" []\n");
+        } else {
+            config.push('\n');
+        }
+        for (name, (field_type, mutable)) in &fields {
+            config.push_str(&format!(
+                "  - name: {name}\n    type: {field_type}\n    mutable: {mutable}\n"
+            ));
+        }
+
+        // Events either carry all custom fields or none
+        let names: Vec<&str> = fields.keys().map(String::as_str).collect();
+        let event = |all_fields: bool| {
+            let fields = if all_fields { names.join(", ") } else { String::new() };
+            format!("    fields: [{fields}]\n")
+        };
+        config.push_str("Events:\n");
+        let (mint, burn, field_update) = events;
+        for (key, event_fields) in
+            [("mint", mint), ("burn", burn), ("field_update", field_update)]
+        {
+            if let Some(all_fields) = event_fields {
+                config.push_str(&format!("  {key}:\n{}", event(all_fields)));
+            }
+        }
+
+        if marketplace {
+            config.push_str("Marketplace: {}\n");
+        }
+
+        if let Some(listings) = listings {
+            config.push_str("Listings:");
+            if listings.is_empty() {
+                config.push_str(" []\n");
+            } else {
+                config.push('\n');
+            }
+            listings.iter().for_each(|l| config.push_str(l));
+        }
+
+        config
+    }
+}
+
+/// Renders the Move module and tests generated from the schema
+fn render(schema: &Schema) -> (String, String) {
+    let mut module = Vec::new();
+    schema.write_move(&mut module).unwrap();
+
+    let mut tests = Vec::new();
+    schema.write_move_tests(&mut tests).unwrap();
+
+    (
+        String::from_utf8(module).unwrap(),
+        String::from_utf8(tests).unwrap(),
+    )
+}
+
+proptest! {
+    // Every case renders the templates four times
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn roundtrip(config in config()) {
+        let schema = Schema::from_reader(config.as_bytes()).unwrap();
+        prop_assume!(schema.validate().is_ok());
+
+        let yaml = serde_yaml::to_string(&schema).unwrap();
+        let from_yaml = Schema::from_reader(yaml.as_bytes()).unwrap();
+        prop_assert_eq!(&from_yaml, &schema);
+
+        let json = serde_json::to_string(&schema).unwrap();
+        let from_json: Schema = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(&from_json, &schema);
+
+        let move_code = render(&schema);
+        prop_assert_eq!(&render(&from_yaml), &move_code);
+        prop_assert_eq!(&render(&from_json), &move_code);
+    }
+}
@@ -0,0 +1,28 @@
+//! Integration tests checking the responses of the HTTP endpoints
+
+use gutenberg::serve;
+
+use axum::http::StatusCode;
+
+use std::fs;
+
+#[tokio::test]
+async fn endpoints() {
+    let config = fs::read_to_string("./examples/suimarines.yaml").unwrap();
+    let expected =
+        fs::read_to_string("./examples/packages/sources/suimarines.move")
+            .unwrap();
+
+    let (status, response) = serve::generate(config.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response["module"], expected);
+
+    let (status, response) = serve::report(config).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response["features"], serde_json::json!([]));
+
+    let (status, response) =
+        serve::validate("NftType: Classic".to_string()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response["kind"], "validation");
+}
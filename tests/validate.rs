@@ -0,0 +1,514 @@
+//! Integration tests checking that semantically invalid configurations are
+//! rejected before any Move code is generated
+
+use gutenberg::prelude::*;
+
+const COLLECTION: &str = r#"
+NftType: "Classic"
+
+Collection:
+  name: "Suimarines"
+  description: "A unique NFT collection of Suimarines on Sui"
+  symbol: "SUIM"
+  tags:
+    - "Art"
+  royalty_fee_bps: "100"
+  url: "https://originbyte.io/"
+"#;
+
+#[test]
+fn listing_schedule() {
+    assert_valid(
+        r#"
+Listings:
+  - start: "2023-01-10T12:00:00Z"
+    end: "2023-01-12T12:00:00+01:00"
+    markets: []
+"#,
+    );
+
+    assert_invalid(
+        r#"
+Listings:
+  - start: "2023-01-12T12:00:00Z"
+    end: "2023-01-12T13:00:00+01:00"
+    markets: []
+"#,
+    );
+}
+
+#[test]
+fn market_token() {
+    assert_valid(
+        r#"
+Listings:
+  - markets:
+      - !FixedPrice
+        price: 100
+        is_whitelisted: false
+
+      - !DutchAuction
+        token: 0x2::coin::Coin<0xab::gold::GOLD>
+        reserve_price: 100
+        is_whitelisted: false
+"#,
+    );
+
+    for token in [
+        "sui::SUI",
+        "sui::sui::SUI<",
+        "0x::sui::SUI",
+        "sui::sui::SUI<>",
+    ] {
+        assert_invalid(&format!(
+            r#"
+Listings:
+  - markets:
+      - !FixedPrice
+        token: "{token}"
+        price: 100
+        is_whitelisted: false
+"#
+        ));
+    }
+}
+
+#[test]
+fn auction_fallback() {
+    let listing = |start: &str, fallback: &str| {
+        format!(
+            r#"
+Listings:
+  - start: "{start}"
+    end: "2023-01-20T12:00:00Z"
+    markets:
+      - !DutchAuction
+        reserve_price: 100
+        is_whitelisted: false
+        fixed_price_after: "{fallback}"
+"#
+        )
+    };
+
+    assert_valid(&listing("2023-01-10T12:00:00Z", "2023-01-12T12:00:00Z"));
+    assert_invalid(&listing("2023-01-12T12:00:00Z", "2023-01-10T12:00:00Z"));
+    assert_invalid(&listing("2023-01-10T12:00:00Z", "2023-01-21T12:00:00Z"));
+}
+
+#[test]
+fn open_edition() {
+    let open_edition = |end: &str| {
+        format!(
+            r#"
+OpenEdition:
+  price: 100
+  url: "https://originbyte.io/edition.png"
+  start: "2023-01-10T12:00:00Z"
+  end: "{end}"
+"#
+        )
+    };
+
+    assert_valid(&open_edition("2023-01-20T12:00:00Z"));
+    assert_invalid(&open_edition("2023-01-10T12:00:00Z"));
+    assert_invalid(&format!(
+        "{}Transferability: \"Soulbound\"\n",
+        open_edition("2023-01-20T12:00:00Z")
+    ));
+    assert_invalid(&format!(
+        "{}Fields:\n  - name: \"level\"\n    type: \"u64\"\n",
+        open_edition("2023-01-20T12:00:00Z")
+    ));
+}
+
+#[test]
+fn requirements() {
+    assert_valid(
+        r#"
+Requires:
+  gutenberg: ">=0.2"
+  nft_protocol: "~0.16"
+"#,
+    );
+
+    let err = parse(
+        r#"
+Requires:
+  gutenberg: ">=99.0"
+  nft_protocol: "^0.17"
+"#,
+    )
+    .validate()
+    .unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Validation);
+    assert_eq!(err.kind().exit_code(), 2);
+    let GutenError::Incompatible(message) = err else {
+        panic!("Expected incompatible versions, found {err:?}");
+    };
+    assert!(message.contains("requires gutenberg >=99.0"));
+    assert!(message
+        .contains("requires nft_protocol ^0.17 but nft_protocol is 0.16.0"));
+}
+
+#[test]
+fn royalty() {
+    let mut schema = parse("");
+
+    for (royalty, bps) in [
+        ("250", 250),
+        ("250bps", 250),
+        ("5%", 500),
+        ("5 %", 500),
+        ("5.5 %", 550),
+        ("2.5%", 250),
+        ("100%", 10_000),
+        ("0.01%", 1),
+    ] {
+        schema.collection.royalty_fee_bps = royalty.into();
+        assert_eq!(schema.collection.royalty_bps(), Ok(bps));
+    }
+
+    for royalty in ["10001", "100.01%", "2.555%", "-5%", "5 percent", ""] {
+        schema.collection.royalty_fee_bps = royalty.into();
+        assert!(matches!(
+            schema.validate(),
+            Err(GutenError::InvalidConfig(_))
+        ));
+    }
+}
+
+#[test]
+fn custom_tags() {
+    let schema = parse("");
+    assert_eq!(schema.collection.tags, [Tag::Art]);
+
+    let mut schema = parse("");
+    schema
+        .collection
+        .tags
+        .push(Tag::Custom("Surfing".to_string()));
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("tags::add_tag(&mut tags, tags::art());"));
+    assert!(output
+        .contains("tags::add_tag(&mut tags, string::utf8(b\"Surfing\"));"));
+
+    let mut schema = parse("");
+    schema
+        .collection
+        .tags
+        .push(Tag::Custom("Sürfing\n".repeat(8)));
+    let modifications = schema.prepare().unwrap();
+    assert_eq!(modifications.len(), 1);
+    assert_eq!(schema.collection.tags[1], Tag::Custom("Sürfing".repeat(8)));
+
+    let mut schema = parse("Sanitization: Strict");
+    schema
+        .collection
+        .tags
+        .push(Tag::Custom("Surfing\n".to_string()));
+    assert!(matches!(
+        schema.prepare(),
+        Err(GutenError::InvalidConfig(_))
+    ));
+
+    let mut schema = parse("");
+    schema.collection.tags.push(Tag::Custom(String::new()));
+    assert!(matches!(
+        schema.validate(),
+        Err(GutenError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn nft_tags() {
+    assert_valid("NftTags: Inherit");
+    assert_valid("NftTags: PerToken");
+
+    let mut schema = parse("NftTags: Inherit");
+    schema.collection.tags.clear();
+    assert!(matches!(
+        schema.validate(),
+        Err(GutenError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn sanitization() {
+    let mut schema = parse("");
+    schema.collection.name = "Sui\u{7}marines".into();
+    schema.collection.cover_url =
+        Some("https://originbyte.io/cover\n.png".into());
+
+    let modifications = schema.sanitize().unwrap();
+    assert_eq!(modifications.len(), 2);
+    assert_eq!(&*schema.collection.name, "Suimarines");
+    assert_eq!(
+        schema.collection.cover_url.as_deref(),
+        Some("https://originbyte.io/cover.png")
+    );
+
+    // Checks apply to the sanitized name, from which the module name of
+    // `tags` is derived
+    let mut schema = parse("");
+    schema.collection.name = "Ta\u{7}gs".into();
+    let Err(GutenError::InvalidConfig(message)) = schema.prepare() else {
+        panic!("Expected the module name to collide");
+    };
+    assert!(message.contains("collides with a module imported"));
+
+    let mut schema = parse("Sanitization: Strict");
+    schema.collection.description = "A".repeat(2000).into();
+
+    assert!(matches!(
+        schema.sanitize(),
+        Err(GutenError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn byte_strings() {
+    let mut schema = parse("");
+    schema.collection.description = r#"Sui"); transfer::transfer(mint_cap, @0xBAD); let _ = string::utf8(b"\"#.into();
+    schema.validate().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(
+        r#"string::utf8(b"Sui\"); transfer::transfer(mint_cap, @0xBAD); let _ = string::utf8(b\"\\"),"#
+    ));
+
+    // Non-ASCII characters are written as their UTF-8 bytes
+    schema.collection.name = "Pokémon".into();
+    schema
+        .collection
+        .tags
+        .push(Tag::Custom("Surf\"s up".to_string()));
+    schema.prepare().unwrap();
+
+    let mut output = Vec::new();
+    schema.write_move(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(r#"string::utf8(b"Pok\xc3\xa9mon"),"#));
+    assert!(output.contains(r#"string::utf8(b"Surf\"s up")"#));
+}
+
+#[test]
+fn urls() {
+    let mut schema = parse("IpfsGateway: https://ipfs.io/");
+    schema.collection.url = "https://bücher.example/shop".into();
+    schema.collection.cover_url = Some("ipfs://QmCid/cover.png".into());
+    schema.validate().unwrap();
+
+    let modifications = schema.sanitize().unwrap();
+    assert_eq!(modifications.len(), 2);
+    assert_eq!(
+        &*schema.collection.url,
+        "https://xn--bcher-kva.example/shop"
+    );
+    assert_eq!(
+        schema.collection.cover_url.as_deref(),
+        Some("https://ipfs.io/ipfs/QmCid/cover.png")
+    );
+
+    let mut schema = parse("Sanitization: Strict");
+    schema.collection.cover_url = Some("ipfs://QmCid/cover.png".into());
+    schema.validate().unwrap();
+    assert!(schema.sanitize().unwrap().is_empty());
+
+    for url in [
+        "originbyte.io",
+        "https://origin byte.io/",
+        "ftp://originbyte.io/",
+        "ipfs:QmCid",
+    ] {
+        schema.collection.url = url.into();
+        assert!(matches!(
+            schema.validate(),
+            Err(GutenError::InvalidConfig(_))
+        ));
+    }
+
+    assert_invalid("IpfsGateway: ipfs://QmCid");
+}
+
+#[test]
+fn module_name() {
+    let mut schema = parse("");
+
+    schema.collection.name = "Cool Apes #1!".into();
+    schema.validate().unwrap();
+    assert_eq!(&*schema.module_name(), "cool_apes_1");
+    assert_eq!(&*schema.witness(), "COOL_APES_1");
+
+    schema.collection.name = "1337 Punks".into();
+    assert_eq!(&*schema.module_name(), "nft_1337_punks");
+
+    schema.collection.name = "Tags".into();
+    assert!(schema.validate().is_err());
+
+    schema.collection.module_name = Some("apes".into());
+    schema.validate().unwrap();
+    assert_eq!(&*schema.module_name(), "apes");
+
+    schema.collection.module_name = Some("Apes".into());
+    assert!(schema.validate().is_err());
+}
+
+#[test]
+fn fields() {
+    assert_valid(
+        r#"
+Fields:
+  - name: level
+    type: u64
+    mutable: true
+  - name: animation_url
+    type: String
+"#,
+    );
+
+    // Collides with the `name` parameter of `mint_nft`
+    assert_invalid(
+        r#"
+Fields:
+  - name: name
+    type: String
+"#,
+    );
+
+    // Collide with the event members, functions, and locals of the module
+    for name in [
+        "nft_id",
+        "field",
+        "fallback_auction",
+        "tags",
+        "mint_open_edition",
+        "close_open_edition",
+    ] {
+        assert_invalid(&format!("Fields:\n  - name: {name}\n    type: u64\n"));
+    }
+
+    // Collide with the functions generated for a mutable collection
+    for (name, mutable) in [
+        ("update_collection_description", "description"),
+        ("update_collection_url", "url"),
+        ("update_collection_royalty_fee_bps", "royalty_fee_bps"),
+    ] {
+        assert_invalid(&format!(
+            "  mutable:\n    {mutable}: true\n\nFields:\n  - name: {name}\n    type: u64\n"
+        ));
+    }
+
+    // Collide with the update functions of the mutable field
+    for name in ["update_level", "apply_level_update"] {
+        assert_invalid(&format!(
+            "Fields:\n  - name: level\n    type: u64\n    mutable: true\n  - name: {name}\n    type: u64\n"
+        ));
+    }
+    assert_valid(
+        r#"
+Fields:
+  - name: level
+    type: u64
+  - name: update_level
+    type: u64
+"#,
+    );
+
+    assert_invalid(
+        r#"
+Fields:
+  - name: level
+    type: u64
+  - name: level
+    type: u8
+"#,
+    );
+}
+
+#[test]
+fn marketplace_fee() {
+    assert_valid("Marketplace:\n  fee_bps: 250");
+    assert_invalid("Marketplace:\n  fee_bps: 10001");
+}
+
+#[test]
+fn transferability() {
+    assert_valid("Transferability: Soulbound");
+
+    assert_invalid(
+        r#"
+Transferability: CreatorRevocable
+
+Burnable:
+  redemption_event: true
+"#,
+    );
+
+    assert_invalid("NftTags: Inherit\n\nBurnable: {}");
+
+    assert_invalid(
+        r#"
+Transferability: CreatorRevocable
+
+Listings:
+  - markets: []
+"#,
+    );
+}
+
+#[test]
+fn events() {
+    assert_valid(
+        r#"
+Fields:
+  - name: level
+    type: u64
+
+Burnable: {}
+
+Events:
+  mint:
+    fields: [level]
+  burn: {}
+"#,
+    );
+
+    assert_invalid(
+        r#"
+Events:
+  mint:
+    fields: [level]
+"#,
+    );
+
+    assert_invalid(
+        r#"
+Events:
+  burn: {}
+"#,
+    );
+}
+
+fn parse(config: &str) -> Schema {
+    Schema::from_reader(format!("{COLLECTION}{config}").as_bytes()).unwrap()
+}
+
+/// Asserts that the config extending the base collection is valid
+fn assert_valid(config: &str) {
+    parse(config).validate().unwrap();
+}
+
+/// Asserts that the config extending the base collection is invalid
+fn assert_invalid(config: &str) {
+    assert!(matches!(
+        parse(config).validate(),
+        Err(GutenError::InvalidConfig(_))
+    ));
+}